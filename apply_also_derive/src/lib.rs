@@ -0,0 +1,252 @@
+//! Procedural macros for `apply_also`. Use them through the `derive`
+//! feature of `apply_also` rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{ Span, TokenStream as TokenStream2 };
+use quote::{ format_ident, quote };
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Fields, FnArg, Generics, Ident, ItemFn, Pat, ReturnType,
+    Token, TraitBoundModifier, Type, TypeParamBound, WherePredicate,
+};
+
+/// Generates a `with_<field>(self, value) -> Self` method for every
+/// named field of a struct. Each method has the same visibility as its
+/// field.
+///
+/// Example:
+/// ```ignore
+/// use apply_also::With;
+///
+/// #[derive(Default, With)]
+/// struct Config {
+///     pub retries: u32,
+///     pub verbose: bool,
+/// }
+///
+/// let config = Config::default().with_retries(3).with_verbose(true);
+/// ```
+#[proc_macro_derive(With)]
+pub fn derive_with(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_with(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand_with(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident, "`With` can only be derived for structs with named fields")),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "`With` can only be derived for structs")),
+    };
+
+    let methods = fields.iter().map(|field| {
+        let vis = &field.vis;
+        let name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let method = format_ident!("with_{}", name);
+        let doc = format!("Returns this value with `{}` set to the given value.", name);
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            #[must_use]
+            #vis fn #method(mut self, value: #ty) -> Self {
+                self.#name = value;
+                self
+            }
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
+}
+
+/// Turns a free function into an extension method on the type of its
+/// first parameter, so it can be called in `apply`/`also` chains.
+///
+/// The generated trait is named after the function in camel case, or
+/// after the identifier given to the attribute. Depending on the first
+/// parameter and the return type, the method is:
+///
+/// - `fn f(it: &mut T, ..)`: `<f>_also(self, ..) -> T`, via `also_mut`
+/// - `fn f(it: &T, ..)`: `<f>_also(self, ..) -> T`, via `also`
+/// - `fn f(it: &mut T, ..) -> R`: `<f>_apply(self, ..) -> R`, via `apply_mut`
+/// - `fn f(it: &T, ..) -> R`: `<f>_apply(self, ..) -> R`, via `apply_ref`
+/// - `fn f(it: T, ..) -> R`: `<f>_apply(self, ..) -> R`, via `apply`
+///
+/// If the referent is unsized, like `str`, a slice, a trait object or a
+/// `?Sized` type parameter, the trait is implemented for the reference
+/// instead, and `<f>_also` returns the reference.
+///
+/// Any further parameters become parameters of the method.
+///
+/// Example:
+/// ```ignore
+/// use apply_also::extension;
+///
+/// #[extension]
+/// fn shrink(v: &mut Vec<u8>) {
+///     v.shrink_to_fit();
+/// }
+///
+/// let buffer = Vec::with_capacity(64).shrink_also();
+/// ```
+#[proc_macro_attribute]
+pub fn extension(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let name = if attribute.is_empty() {
+        None
+    } else {
+        Some(parse_macro_input!(attribute as Ident))
+    };
+    let function = parse_macro_input!(item as ItemFn);
+    expand_extension(name, function).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand_extension(name: Option<Ident>, function: ItemFn) -> Result<TokenStream2, Error> {
+    let signature = &function.sig;
+    let ident = &signature.ident;
+    let mut inputs = signature.inputs.iter();
+
+    let receiver = match inputs.next() {
+        Some(FnArg::Typed(receiver)) => receiver,
+        Some(FnArg::Receiver(receiver)) => {
+            return Err(Error::new_spanned(receiver, "`extension` can only be applied to free functions"))
+        }
+        None => return Err(Error::new_spanned(signature, "`extension` requires at least one parameter")),
+    };
+
+    let mut parameters = Vec::new();
+    let mut arguments = Vec::new();
+    for (index, input) in inputs.enumerate() {
+        match input {
+            FnArg::Typed(input) => {
+                let argument = match &*input.pat {
+                    Pat::Ident(pat) => pat.ident.clone(),
+                    _ => format_ident!("argument{}", index),
+                };
+                let ty = &input.ty;
+                parameters.push(quote!(#argument: #ty));
+                arguments.push(argument);
+            }
+            FnArg::Receiver(receiver) => return Err(Error::new_spanned(receiver, "unexpected `self` parameter")),
+        }
+    }
+
+    let returns_unit = match &signature.output {
+        ReturnType::Default => true,
+        ReturnType::Type(_, ty) => matches!(&**ty, Type::Tuple(tuple) if tuple.elems.is_empty()),
+    };
+    let (target, body, suffix, output) = match &*receiver.ty {
+        Type::Reference(reference) if is_unsized(&reference.elem, &signature.generics) => {
+            // `self` can not be unsized, so the trait is implemented for
+            // the reference itself, which is passed on directly.
+            let target = quote!(#reference);
+            let it = match reference.mutability {
+                Some(_) => quote!(&mut *self),
+                None => quote!(self),
+            };
+            if returns_unit {
+                (target, quote!({ #ident(#it, #(#arguments),*); self }), "also", quote!(Self))
+            } else {
+                let output = match &signature.output {
+                    ReturnType::Type(_, ty) => quote!(#ty),
+                    ReturnType::Default => quote!(()),
+                };
+                (target, quote!(#ident(self, #(#arguments),*)), "apply", output)
+            }
+        }
+        Type::Reference(reference) => {
+            // Calls the function directly instead of through `Also` and
+            // `Apply`, which may not be implemented for the type without
+            // the `blanket` feature.
+            let (binding, it) = match reference.mutability {
+                Some(_) => (quote!(mut it), quote!(&mut it)),
+                None => (quote!(it), quote!(&it)),
+            };
+            let body = if returns_unit {
+                quote!({ let #binding = self; #ident(#it, #(#arguments),*); it })
+            } else {
+                quote!({ let #binding = self; #ident(#it, #(#arguments),*) })
+            };
+            let output = match &signature.output {
+                ReturnType::Type(_, ty) if !returns_unit => quote!(#ty),
+                _ => quote!(Self),
+            };
+            let elem = &reference.elem;
+            (quote!(#elem), body, if returns_unit { "also" } else { "apply" }, output)
+        }
+        ty => {
+            let output = match &signature.output {
+                ReturnType::Type(_, ty) => quote!(#ty),
+                ReturnType::Default => quote!(()),
+            };
+            (quote!(#ty), quote!(#ident(self, #(#arguments),*)), "apply", output)
+        }
+    };
+
+    let trait_ident = name.unwrap_or_else(|| Ident::new(&camel_case(&ident.to_string()), Span::call_site()));
+    let method = format_ident!("{}_{}", ident, suffix);
+    let vis = &function.vis;
+    let (impl_generics, ty_generics, where_clause) = signature.generics.split_for_impl();
+    let doc = format!("Extension trait generated from [`{}`].", ident);
+    let method_doc = format!("Calls [`{}`] with this value in an `{}` chain.", ident, suffix);
+
+    Ok(quote! {
+        #function
+
+        #[doc = #doc]
+        #vis trait #trait_ident #impl_generics #where_clause {
+            #[doc = #method_doc]
+            fn #method(self, #(#parameters),*) -> #output;
+        }
+
+        impl #impl_generics #trait_ident #ty_generics for #target #where_clause {
+            #[inline]
+            fn #method(self, #(#parameters),*) -> #output {
+                #body
+            }
+        }
+    })
+}
+
+/// Returns whether the given type is known to be unsized: `str`, a
+/// slice, a trait object, or a type parameter bounded by `?Sized`.
+fn is_unsized(ty: &Type, generics: &Generics) -> bool {
+    match ty {
+        Type::Slice(_) | Type::TraitObject(_) => true,
+        Type::Paren(paren) => is_unsized(&paren.elem, generics),
+        Type::Path(path) if path.qself.is_none() => {
+            let ident = match path.path.get_ident() {
+                Some(ident) => ident,
+                None => return false,
+            };
+            let maybe_unsized = |bounds: &Punctuated<TypeParamBound, Token![+]>| {
+                bounds.iter().any(|bound| matches!(bound, TypeParamBound::Trait(bound) if matches!(bound.modifier, TraitBoundModifier::Maybe(_))))
+            };
+            ident == "str"
+                || generics.type_params().any(|param| param.ident == *ident && maybe_unsized(&param.bounds))
+                || generics.where_clause.iter().flat_map(|clause| &clause.predicates).any(|predicate| {
+                    matches!(predicate, WherePredicate::Type(predicate)
+                        if matches!(&predicate.bounded_ty, Type::Path(bounded) if bounded.path.is_ident(ident))
+                            && maybe_unsized(&predicate.bounds))
+                })
+        }
+        _ => false,
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+        })
+        .collect()
+}
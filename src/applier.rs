@@ -0,0 +1,179 @@
+//! Reusable transformations, for passing functions across API
+//! boundaries and storing them in collections.
+//!
+//! ```
+//! use apply_also::ApplyApplier;
+//! use apply_also::applier::Applier;
+//!
+//! let trim = Applier::new(|it: &str| it.trim());
+//! let parse = trim.clone().then(str::parse::<u16>);
+//! assert_eq!(" x ".apply_with_applier(&trim), "x");
+//! assert_eq!(" 80 ".apply_with_applier(&parse), Ok(80));
+//! ```
+
+use crate::ApplyAlso;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// A transformation from `T` to `R`, applied with [`Applier::call`] or
+/// [`ApplyApplier::apply_with_applier`].
+///
+/// An applier is `Clone` if its function is, including appliers
+/// composed with [`Applier::then`]. Appliers of different functions
+/// have different types; [`Applier::boxed`] erases the type of the
+/// function, so the appliers can be stored in a collection.
+pub struct Applier<T, R, F> {
+    function: F,
+    _marker: PhantomData<fn(T) -> R>,
+}
+
+/// A function an [`Applier`] can apply: any `Fn(T) -> R`, or functions
+/// composed with [`Applier::then`].
+pub trait ApplierFn<T> {
+    /// The result of the function.
+    type Output;
+
+    /// Applies the function to the given value.
+    fn call(&self, value: T) -> Self::Output;
+}
+
+impl<T, R, F: Fn(T) -> R> ApplierFn<T> for F {
+    type Output = R;
+
+    #[inline(always)]
+    fn call(&self, value: T) -> R {
+        self(value)
+    }
+}
+
+/// The function of an applier composed with [`Applier::then`],
+/// applying `G` to the result of `F`.
+#[derive(Clone, Copy, Debug)]
+pub struct Then<F, G> {
+    first: F,
+    next: G,
+}
+
+impl<T, F: ApplierFn<T>, G: ApplierFn<F::Output>> ApplierFn<T> for Then<F, G> {
+    type Output = G::Output;
+
+    #[inline(always)]
+    fn call(&self, value: T) -> G::Output {
+        self.next.call(self.first.call(value))
+    }
+}
+
+impl<T, R, F: Fn(T) -> R> Applier<T, R, F> {
+    /// Creates an applier of the given function.
+    #[inline(always)]
+    pub fn new(function: F) -> Self {
+        Applier { function, _marker: PhantomData }
+    }
+}
+
+impl<T, R, F: ApplierFn<T, Output = R>> Applier<T, R, F> {
+    /// Applies the function to the given value and returns the result.
+    #[inline(always)]
+    pub fn call(&self, value: T) -> R {
+        self.function.call(value)
+    }
+
+    /// Returns an applier applying this applier's function, then the
+    /// given function to the result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::applier::Applier;
+    /// let len = Applier::new(|it: String| it.len()).then(|it| it * 2);
+    /// assert_eq!(len.clone().call("abc".to_string()), 6);
+    /// ```
+    #[inline(always)]
+    pub fn then<S, G: Fn(R) -> S>(self, next: G) -> Applier<T, S, Then<F, G>> {
+        Applier { function: Then { first: self.function, next }, _marker: PhantomData }
+    }
+
+    /// Returns the function of this applier.
+    #[inline(always)]
+    pub fn into_inner(self) -> F {
+        self.function
+    }
+
+    /// Returns an applier of the boxed function, whose type does not
+    /// depend on the function.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::applier::Applier;
+    /// let steps = vec![
+    ///     Applier::new(|it: i32| it + 1).boxed(),
+    ///     Applier::new(|it: i32| it * 10).then(|it| it - 1).boxed(),
+    /// ];
+    /// assert_eq!(steps.iter().fold(1, |it, step| step.call(it)), 19);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn boxed<'a>(self) -> Applier<T, R, Box<dyn Fn(T) -> R + 'a>> where F: 'a {
+        let function = self.function;
+        Applier::new(Box::new(move |value| function.call(value)))
+    }
+}
+
+impl<T, R, F: Clone> Clone for Applier<T, R, F> {
+    fn clone(&self) -> Self {
+        Applier { function: self.function.clone(), _marker: PhantomData }
+    }
+}
+
+impl<T, R, F> fmt::Debug for Applier<T, R, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Applier").finish_non_exhaustive()
+    }
+}
+
+pub trait ApplyApplier<T> {
+    /// Applies the function of the given applier to this value and
+    /// returns the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyApplier;
+    /// # use apply_also::applier::Applier;
+    /// let validate = Applier::new(|it: u16| if it > 0 { Ok(it) } else { Err("port is zero") });
+    /// assert_eq!(8080.apply_with_applier(&validate), Ok(8080));
+    /// assert_eq!(0.apply_with_applier(&validate), Err("port is zero"));
+    /// ```
+    fn apply_with_applier<R, F: ApplierFn<T, Output = R>>(self, applier: &Applier<T, R, F>) -> R;
+}
+
+impl<T: ApplyAlso> ApplyApplier<T> for T {
+    #[inline(always)]
+    fn apply_with_applier<R, F: ApplierFn<T, Output = R>>(self, applier: &Applier<T, R, F>) -> R {
+        applier.call(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn then() {
+        let increment = Applier::new(|it: i32| it + 1);
+        let applier = increment.clone().then(|it| it * 2).then(|it| it.to_string());
+        let cloned = applier.clone();
+        assert_eq!(1.apply_with_applier(&applier), "4");
+        assert_eq!(cloned.call(2), "6");
+        assert_eq!(increment.call(2), 3);
+        assert_eq!(format!("{:?}", applier), "Applier { .. }");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed() {
+        let offset = 3;
+        let appliers = vec![Applier::new(move |it: i32| it + offset).boxed(), Applier::new(i32::abs).boxed()];
+        assert_eq!(appliers.iter().map(|it| (-5).apply_with_applier(it)).collect::<Vec<_>>(), vec![-2, 5]);
+        assert_eq!(appliers.into_iter().next().unwrap().into_inner()(0), 3);
+    }
+}
@@ -0,0 +1,111 @@
+//! `also` variants asserting invariants on the value.
+
+use crate::ApplyAlso;
+use core::fmt::Debug;
+
+pub trait AlsoAssert<T> {
+    /// Asserts that the predicate holds for this value, panicking with
+    /// the given message and the value otherwise. Returns the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, AlsoAssert };
+    /// let x = 256.apply(|it| it * 2).also_assert(|it| *it > 0, "must be positive");
+    /// assert_eq!(x, 512);
+    /// ```
+    fn also_assert(self, predicate: impl FnOnce(&T) -> bool, message: &str) -> T where T: Debug;
+
+    /// Asserts that this value equals the expected value, panicking
+    /// with both values otherwise. Returns the value.
+    ///
+    /// Example:
+    /// ```should_panic
+    /// # use apply_also::{ Apply, AlsoAssert };
+    /// let x = "yes".apply(str::len).also_assert_eq(2);
+    /// ```
+    fn also_assert_eq<U: Debug>(self, expected: U) -> T where T: PartialEq<U> + Debug;
+
+    /// Like [`also_assert`](AlsoAssert::also_assert), but only checked
+    /// in debug builds, like `debug_assert!`.
+    fn also_debug_assert(self, predicate: impl FnOnce(&T) -> bool, message: &str) -> T where T: Debug;
+
+    /// Like [`also_assert_eq`](AlsoAssert::also_assert_eq), but only
+    /// checked in debug builds, like `debug_assert_eq!`.
+    fn also_debug_assert_eq<U: Debug>(self, expected: U) -> T where T: PartialEq<U> + Debug;
+}
+
+impl<T: ApplyAlso> AlsoAssert<T> for T {
+    #[inline(always)]
+    #[track_caller]
+    fn also_assert(self, predicate: impl FnOnce(&T) -> bool, message: &str) -> T where T: Debug {
+        if !predicate(&self) {
+            panic!("assertion failed: {}\n value: {:?}", message, self);
+        }
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_assert_eq<U: Debug>(self, expected: U) -> T where T: PartialEq<U> + Debug {
+        if self != expected {
+            panic!("assertion `value == expected` failed\n    value: {:?}\n expected: {:?}", self, expected);
+        }
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_debug_assert(self, predicate: impl FnOnce(&T) -> bool, message: &str) -> T where T: Debug {
+        if cfg!(debug_assertions) {
+            self.also_assert(predicate, message)
+        } else {
+            self
+        }
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_debug_assert_eq<U: Debug>(self, expected: U) -> T where T: PartialEq<U> + Debug {
+        if cfg!(debug_assertions) {
+            self.also_assert_eq(expected)
+        } else {
+            self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+        let payload = panic::catch_unwind(f).unwrap_err();
+        payload.downcast_ref::<String>().cloned().unwrap()
+    }
+
+    #[test]
+    fn also_assert() {
+        assert_eq!(vec![1].also_assert(|it| !it.is_empty(), "non-empty"), vec![1]);
+        let message = panic_message(|| {
+            Vec::<i32>::new().also_assert(|it| !it.is_empty(), "non-empty");
+        });
+        assert_eq!(message, "assertion failed: non-empty\n value: []");
+    }
+
+    #[test]
+    fn also_assert_eq() {
+        assert_eq!("a".to_string().also_assert_eq("a"), "a");
+        let message = panic_message(|| {
+            3.also_assert_eq(4);
+        });
+        assert_eq!(message, "assertion `value == expected` failed\n    value: 3\n expected: 4");
+    }
+
+    #[test]
+    fn also_debug_assert() {
+        assert_eq!(1.also_debug_assert(|it| *it == 1, "one").also_debug_assert_eq(1), 1);
+        let failed = panic::catch_unwind(|| 1.also_debug_assert_eq(2)).is_err();
+        assert_eq!(failed, cfg!(debug_assertions));
+    }
+}
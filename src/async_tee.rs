@@ -0,0 +1,204 @@
+//! `tee`-style adapters for asynchronous readers and writers,
+//! duplicating every byte passing through to a secondary sink.
+//!
+//! The sink is a blocking `std::io::Write`, which is written to from
+//! within `poll_read` and `poll_write`. It should therefore be fast,
+//! like an in-memory buffer or a buffered log file.
+
+use core::pin::Pin;
+use core::task::{ Context, Poll };
+use futures_io::{ AsyncRead, AsyncWrite };
+use std::io::{ self, Write };
+
+pub trait AsyncReadTeeExt: AsyncRead + Sized {
+    /// Wraps this reader, so that every byte read from it is also
+    /// written to the given sink.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AsyncReadTeeExt;
+    /// # use futures_io::AsyncRead;
+    /// # fn run(download: impl AsyncRead) -> std::io::Result<()> {
+    /// let log = std::fs::File::create("download.log")?;
+    /// let download = download.tee_read(log);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn tee_read<W: Write>(self, sink: W) -> AsyncTeeReader<Self, W>;
+}
+
+pub trait AsyncWriteTeeExt: AsyncWrite + Sized {
+    /// Wraps this writer, so that every byte written to it is also
+    /// written to the given sink. Only the bytes accepted by this
+    /// writer are passed on.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AsyncWriteTeeExt;
+    /// # use futures_io::AsyncWrite;
+    /// # fn run(upload: impl AsyncWrite) {
+    /// let upload = upload.tee_write(Vec::new());
+    /// # }
+    /// ```
+    fn tee_write<W: Write>(self, sink: W) -> AsyncTeeWriter<Self, W>;
+}
+
+/// Reader returned by [`AsyncReadTeeExt::tee_read`].
+#[derive(Debug)]
+pub struct AsyncTeeReader<R, W> {
+    reader: R,
+    sink: W,
+}
+
+/// Writer returned by [`AsyncWriteTeeExt::tee_write`].
+#[derive(Debug)]
+pub struct AsyncTeeWriter<W, S> {
+    writer: W,
+    sink: S,
+}
+
+impl<R: AsyncRead> AsyncReadTeeExt for R {
+    fn tee_read<W: Write>(self, sink: W) -> AsyncTeeReader<Self, W> {
+        AsyncTeeReader { reader: self, sink }
+    }
+}
+
+impl<W: AsyncWrite> AsyncWriteTeeExt for W {
+    fn tee_write<S: Write>(self, sink: S) -> AsyncTeeWriter<Self, S> {
+        AsyncTeeWriter { writer: self, sink }
+    }
+}
+
+impl<R, W> AsyncTeeReader<R, W> {
+    /// Returns the wrapped reader and the sink.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.sink)
+    }
+}
+
+impl<W, S> AsyncTeeWriter<W, S> {
+    /// Returns the wrapped writer and the sink.
+    pub fn into_inner(self) -> (W, S) {
+        (self.writer, self.sink)
+    }
+}
+
+impl<R: AsyncRead, W: Write> AsyncRead for AsyncTeeReader<R, W> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        // SAFETY: `reader` is structurally pinned and never moved out of,
+        // `sink` is not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let reader = unsafe { Pin::new_unchecked(&mut this.reader) };
+        let read = reader.poll_read(cx, buf);
+        if let Poll::Ready(Ok(read)) = read {
+            this.sink.write_all(&buf[..read])?;
+        }
+        read
+    }
+}
+
+impl<W: AsyncWrite, S: Write> AsyncTeeWriter<W, S> {
+    fn project(self: Pin<&mut Self>) -> (Pin<&mut W>, &mut S) {
+        // SAFETY: `writer` is structurally pinned and never moved out of,
+        // `sink` is not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        (unsafe { Pin::new_unchecked(&mut this.writer) }, &mut this.sink)
+    }
+}
+
+impl<W: AsyncWrite, S: Write> AsyncWrite for AsyncTeeWriter<W, S> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let (writer, sink) = self.project();
+        let written = writer.poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = written {
+            sink.write_all(&buf[..written])?;
+        }
+        written
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let (writer, sink) = self.project();
+        let flushed = writer.poll_flush(cx);
+        if let Poll::Ready(Ok(())) = flushed {
+            sink.flush()?;
+        }
+        flushed
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let (writer, sink) = self.project();
+        let closed = writer.poll_close(cx);
+        if let Poll::Ready(Ok(())) = closed {
+            sink.flush()?;
+        }
+        closed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::pin;
+    use std::task::Waker;
+
+    #[test]
+    fn tee_reader() {
+        let mut reader = pin!((&b"async reads"[..]).tee_read(Vec::new()));
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut buf = [0; 5];
+        let mut read = Vec::new();
+        while let Poll::Ready(Ok(n @ 1..)) = reader.as_mut().poll_read(&mut cx, &mut buf) {
+            read.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(read, b"async reads");
+        assert_eq!(reader.sink, b"async reads");
+    }
+
+    #[test]
+    fn tee_writer() {
+        let mut writer = pin!(Vec::new().tee_write(Vec::new()));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(matches!(writer.as_mut().poll_write(&mut cx, b"abc"), Poll::Ready(Ok(3))));
+        assert!(matches!(writer.as_mut().poll_close(&mut cx), Poll::Ready(Ok(()))));
+        assert_eq!(writer.writer, b"abc");
+        assert_eq!(writer.sink, b"abc");
+    }
+
+    /// Reads from `input` and writes to `output`, like a socket.
+    struct Duplex {
+        input: &'static [u8],
+        output: Vec<u8>,
+    }
+
+    impl AsyncRead for Duplex {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.get_mut().input).poll_read(cx, buf)
+        }
+    }
+
+    impl AsyncWrite for Duplex {
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.get_mut().output).poll_write(cx, buf)
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn read_and_write() {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut writer = pin!(Duplex { input: b"in", output: Vec::new() }.tee_write(Vec::new()));
+        assert!(matches!(writer.as_mut().poll_write(&mut cx, b"out"), Poll::Ready(Ok(3))));
+        assert_eq!(writer.sink, b"out");
+
+        let mut reader = pin!(Duplex { input: b"in", output: Vec::new() }.tee_read(Vec::new()));
+        assert!(matches!(reader.as_mut().poll_read(&mut cx, &mut [0; 4]), Poll::Ready(Ok(2))));
+        assert_eq!(reader.sink, b"in");
+    }
+}
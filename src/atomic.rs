@@ -0,0 +1,119 @@
+//! `apply` and `also` variants for the atomic integer types.
+
+use core::sync::atomic::Ordering;
+
+pub trait AtomicApplyExt {
+    /// The integer type of this atomic.
+    type Value;
+
+    /// Replaces the value with the result of applying the given
+    /// function to it, retrying if the value was changed concurrently.
+    /// Returns the previous value, like the other `fetch_` methods.
+    ///
+    /// Built on `fetch_update`, so the function may be called multiple
+    /// times. `ordering` describes the store, the load uses the
+    /// strongest ordering allowed for loads that is implied by it.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AtomicApplyExt;
+    /// use core::sync::atomic::{ AtomicU64, Ordering };
+    ///
+    /// let counter = AtomicU64::new(3);
+    /// assert_eq!(counter.fetch_apply(Ordering::SeqCst, |it| it * 2), 3);
+    /// assert_eq!(counter.load(Ordering::SeqCst), 6);
+    /// ```
+    fn fetch_apply(&self, ordering: Ordering, function: impl FnMut(Self::Value) -> Self::Value) -> Self::Value;
+
+    /// Loads the value and applies the given function to it.
+    /// Returns this atomic, for chaining.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AtomicApplyExt;
+    /// use core::sync::atomic::{ AtomicUsize, Ordering };
+    ///
+    /// let hits = AtomicUsize::new(7);
+    /// hits.also_load(Ordering::Relaxed, |it| println!("{} hits", it))
+    ///     .fetch_add(1, Ordering::Relaxed);
+    /// assert_eq!(hits.load(Ordering::Relaxed), 8);
+    /// ```
+    fn also_load(&self, ordering: Ordering, function: impl FnOnce(Self::Value)) -> &Self;
+}
+
+/// Returns the ordering to load with, for a read-modify-write
+/// operation storing with the given ordering.
+#[inline(always)]
+fn load_ordering(ordering: Ordering) -> Ordering {
+    match ordering {
+        Ordering::Release => Ordering::Relaxed,
+        Ordering::AcqRel => Ordering::Acquire,
+        ordering => ordering,
+    }
+}
+
+macro_rules! impl_atomic_apply_ext {
+    ($($width:literal => $($atomic:ident($value:ty)),+;)+) => {
+        $($(
+            #[cfg(target_has_atomic = $width)]
+            impl AtomicApplyExt for core::sync::atomic::$atomic {
+                type Value = $value;
+
+                #[inline(always)]
+                fn fetch_apply(&self, ordering: Ordering, mut function: impl FnMut($value) -> $value) -> $value {
+                    match self.fetch_update(ordering, load_ordering(ordering), |it| Some(function(it))) {
+                        Ok(previous) | Err(previous) => previous,
+                    }
+                }
+
+                #[inline(always)]
+                fn also_load(&self, ordering: Ordering, function: impl FnOnce($value)) -> &Self {
+                    function(self.load(ordering));
+                    self
+                }
+            }
+        )+)+
+    };
+}
+
+impl_atomic_apply_ext! {
+    "8" => AtomicU8(u8), AtomicI8(i8);
+    "16" => AtomicU16(u16), AtomicI16(i16);
+    "32" => AtomicU32(u32), AtomicI32(i32);
+    "64" => AtomicU64(u64), AtomicI64(i64);
+    "ptr" => AtomicUsize(usize), AtomicIsize(isize);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::{ AtomicI32, AtomicU64 };
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn fetch_apply() {
+        let counter = Arc::new(AtomicU64::new(0));
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        counter.fetch_apply(Ordering::AcqRel, |it| it + 1);
+                    }
+                })
+            })
+            .collect();
+        threads.into_iter().for_each(|it| it.join().unwrap());
+        assert_eq!(counter.load(Ordering::Acquire), 400);
+    }
+
+    #[test]
+    fn also_load() {
+        let mut seen = 0;
+        let value = AtomicI32::new(-2);
+        value.also_load(Ordering::Relaxed, |it| seen = it).fetch_apply(Ordering::Relaxed, |it| it * it);
+        assert_eq!(seen, -2);
+        assert_eq!(value.load(Ordering::Relaxed), 4);
+    }
+}
@@ -0,0 +1,238 @@
+//! Memoizing pure but expensive functions, with a bounded cache that
+//! evicts the least recently used results.
+//!
+//! ```
+//! use apply_also::{ Apply, ApplyCached };
+//! use apply_also::cache::Cache;
+//!
+//! fn slow_square(x: u64) -> u64 { x * x }
+//!
+//! let cache = Cache::new(128);
+//! let x = 12u64.apply_cached(&cache, slow_square).apply(|it| it + 1);
+//! let y = 12u64.apply_cached(&cache, |_| unreachable!("cached"));
+//! assert_eq!((x, y), (145, 144));
+//! ```
+
+use crate::ApplyAlso;
+use core::fmt;
+use core::hash::Hash;
+use std::collections::{ BTreeMap, HashMap };
+use std::sync::{ Mutex, MutexGuard, PoisonError };
+
+/// A cache of function results keyed by their input, holding at most
+/// `capacity` results. When full, the least recently used result is
+/// evicted.
+///
+/// A cache can be shared between threads.
+pub struct Cache<T, R> {
+    capacity: usize,
+    entries: Mutex<Lru<T, R>>,
+}
+
+struct Lru<T, R> {
+    /// The results with the tick they were last used at.
+    results: HashMap<T, (R, u64)>,
+    /// The inputs by the tick they were last used at.
+    order: BTreeMap<u64, T>,
+    tick: u64,
+}
+
+impl<T: Hash + Eq + Clone, R: Clone> Lru<T, R> {
+    fn get(&mut self, input: &T) -> Option<R> {
+        self.tick += 1;
+        let (result, used) = self.results.get_mut(input)?;
+        let input = self.order.remove(used).expect("inputs and order are in sync");
+        *used = self.tick;
+        self.order.insert(self.tick, input);
+        Some(result.clone())
+    }
+
+    fn insert(&mut self, input: T, result: R, capacity: usize) {
+        self.tick += 1;
+        if let Some((_, used)) = self.results.insert(input.clone(), (result, self.tick)) {
+            self.order.remove(&used);
+        }
+        self.order.insert(self.tick, input);
+        while self.results.len() > capacity {
+            let (_, oldest) = self.order.pop_first().expect("inputs and order are in sync");
+            self.results.remove(&oldest);
+        }
+    }
+}
+
+impl<T, R> Cache<T, R> {
+    /// Creates an empty cache holding at most `capacity` results.
+    pub fn new(capacity: usize) -> Self {
+        Cache {
+            capacity,
+            entries: Mutex::new(Lru { results: HashMap::new(), order: BTreeMap::new(), tick: 0 }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Lru<T, R>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the maximum number of cached results.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.lock().results.len()
+    }
+
+    /// Returns whether no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.lock().results.is_empty()
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.results.clear();
+        entries.order.clear();
+    }
+
+    /// Returns the cached result for the given input, or computes it
+    /// with the given function and caches it.
+    ///
+    /// The function runs without holding the lock of the cache, so it
+    /// may use the cache itself, e.g. for recursion.
+    pub fn get_or_compute(&self, input: T, function: impl FnOnce(T) -> R) -> R where T: Hash + Eq + Clone, R: Clone {
+        if let Some(result) = self.lock().get(&input) {
+            return result;
+        }
+        let result = function(input.clone());
+        if self.capacity > 0 {
+            self.lock().insert(input, result.clone(), self.capacity);
+        }
+        result
+    }
+}
+
+impl<T, R> fmt::Debug for Cache<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// A function memoized with a [`Cache`].
+///
+/// Example:
+/// ```
+/// # use apply_also::cache::Cached;
+/// let parse = Cached::new(16, |it: String| it.parse::<i32>().ok());
+/// assert_eq!(parse.call("42".to_string()), Some(42));
+/// assert_eq!(parse.cache().len(), 1);
+/// ```
+pub struct Cached<T, R, F> {
+    function: F,
+    cache: Cache<T, R>,
+}
+
+impl<T, R, F: Fn(T) -> R> Cached<T, R, F> {
+    /// Wraps the given function, caching at most `capacity` results.
+    pub fn new(capacity: usize, function: F) -> Self {
+        Cached { function, cache: Cache::new(capacity) }
+    }
+
+    /// Calls the function with the given input, or returns the cached
+    /// result for it.
+    pub fn call(&self, input: T) -> R where T: Hash + Eq + Clone, R: Clone {
+        self.cache.get_or_compute(input, &self.function)
+    }
+
+    /// Returns the cache of this function.
+    pub fn cache(&self) -> &Cache<T, R> {
+        &self.cache
+    }
+}
+
+impl<T, R, F> fmt::Debug for Cached<T, R, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cached").field("cache", &self.cache).finish_non_exhaustive()
+    }
+}
+
+pub trait ApplyCached<T> {
+    /// Applies the given function to this value and returns the
+    /// result, or returns the result cached for this value.
+    /// Consumes the value.
+    fn apply_cached<R: Clone>(self, cache: &Cache<T, R>, function: impl FnOnce(T) -> R) -> R where T: Hash + Eq + Clone;
+}
+
+impl<T: ApplyAlso> ApplyCached<T> for T {
+    #[inline(always)]
+    fn apply_cached<R: Clone>(self, cache: &Cache<T, R>, function: impl FnOnce(T) -> R) -> R where T: Hash + Eq + Clone {
+        cache.get_or_compute(self, function)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn apply_cached() {
+        let calls = Cell::new(0);
+        let cache = Cache::new(2);
+        let double = |it: i32| {
+            calls.set(calls.get() + 1);
+            it * 2
+        };
+        assert_eq!(1.apply_cached(&cache, double), 2);
+        assert_eq!(1.apply_cached(&cache, double), 2);
+        assert_eq!(calls.get(), 1);
+
+        // 3 evicts 2, as 1 was used more recently
+        2.apply_cached(&cache, double);
+        1.apply_cached(&cache, double);
+        3.apply_cached(&cache, double);
+        assert_eq!(calls.get(), 3);
+        1.apply_cached(&cache, double);
+        assert_eq!(calls.get(), 3);
+        2.apply_cached(&cache, double);
+        assert_eq!(calls.get(), 4);
+        assert_eq!(cache.len(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let cache = Cache::new(0);
+        assert_eq!("a".apply_cached(&cache, str::len), 1);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn recursive() {
+        fn fib(cache: &Cache<u64, u64>, n: u64) -> u64 {
+            if n < 2 { n } else { n.apply_cached(cache, |n| fib(cache, n - 1) + fib(cache, n - 2)) }
+        }
+
+        let cache = Cache::new(100);
+        assert_eq!(fib(&cache, 80), 23_416_728_348_467_685);
+        assert_eq!(cache.len(), 79);
+    }
+
+    #[test]
+    fn cached() {
+        let calls = Cell::new(0);
+        let len = Cached::new(4, |it: String| {
+            calls.set(calls.get() + 1);
+            it.len()
+        });
+        assert_eq!(len.call("ab".to_string()), 2);
+        assert_eq!(len.call("ab".to_string()), 2);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(format!("{:?}", len), "Cached { cache: Cache { capacity: 4, len: 1 }, .. }");
+    }
+}
@@ -0,0 +1,211 @@
+//! `apply` and `also` variants for `RefCell`, `Cell`, `OnceCell` and
+//! `OnceLock`.
+
+use crate::init;
+use core::cell::{ BorrowError, BorrowMutError, Cell, OnceCell, RefCell };
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+pub trait RefCellApplyExt<T: ?Sized> {
+    /// Immutably borrows the value, applies the given function to it
+    /// and returns the result. The borrow ends before returning.
+    ///
+    /// Panics if the value is currently mutably borrowed, see
+    /// [`try_apply_borrow`](RefCellApplyExt::try_apply_borrow).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::RefCellApplyExt;
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(vec![1, 2, 3]);
+    /// assert_eq!(cell.apply_borrow(|it| it.len()), 3);
+    /// ```
+    fn apply_borrow<R>(&self, function: impl FnOnce(&T) -> R) -> R;
+
+    /// Mutably borrows the value and applies the given function to it.
+    /// Returns this cell, for chaining. The borrow ends before returning.
+    ///
+    /// Panics if the value is currently borrowed, see
+    /// [`try_also_borrow_mut`](RefCellApplyExt::try_also_borrow_mut).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::RefCellApplyExt;
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(vec![3, 1]);
+    /// cell.also_borrow_mut(|it| it.push(2)).also_borrow_mut(|it| it.sort());
+    /// assert_eq!(*cell.borrow(), vec![1, 2, 3]);
+    /// ```
+    fn also_borrow_mut(&self, function: impl FnOnce(&mut T)) -> &Self;
+
+    /// Immutably borrows the value, applies the given function to it
+    /// and returns the result. If the value is currently mutably
+    /// borrowed, the function is not called and the error is returned.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::RefCellApplyExt;
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(5);
+    /// let _writer = cell.borrow_mut();
+    /// assert!(cell.try_apply_borrow(|it| *it).is_err());
+    /// ```
+    fn try_apply_borrow<R>(&self, function: impl FnOnce(&T) -> R) -> Result<R, BorrowError>;
+
+    /// Mutably borrows the value and applies the given function to it.
+    /// Returns this cell, for chaining. If the value is currently
+    /// borrowed, the function is not called and the error is returned.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::RefCellApplyExt;
+    /// use core::cell::RefCell;
+    ///
+    /// let cell = RefCell::new(5);
+    /// assert!(cell.try_also_borrow_mut(|it| *it += 1).is_ok());
+    /// assert_eq!(*cell.borrow(), 6);
+    /// ```
+    fn try_also_borrow_mut(&self, function: impl FnOnce(&mut T)) -> Result<&Self, BorrowMutError>;
+}
+
+impl<T: ?Sized> RefCellApplyExt<T> for RefCell<T> {
+    #[inline(always)]
+    #[track_caller]
+    fn apply_borrow<R>(&self, function: impl FnOnce(&T) -> R) -> R {
+        function(&self.borrow())
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_borrow_mut(&self, function: impl FnOnce(&mut T)) -> &Self {
+        function(&mut self.borrow_mut());
+        self
+    }
+
+    #[inline(always)]
+    fn try_apply_borrow<R>(&self, function: impl FnOnce(&T) -> R) -> Result<R, BorrowError> {
+        self.try_borrow().map(|guard| function(&guard))
+    }
+
+    #[inline(always)]
+    fn try_also_borrow_mut(&self, function: impl FnOnce(&mut T)) -> Result<&Self, BorrowMutError> {
+        let mut guard = self.try_borrow_mut()?;
+        function(&mut guard);
+        Ok(self)
+    }
+}
+
+pub trait CellApplyExt<T> {
+    /// Replaces the contained value with the result of applying the
+    /// given function to it, and returns the new value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::CellApplyExt;
+    /// use core::cell::Cell;
+    ///
+    /// let counter = Cell::new(1);
+    /// assert_eq!(counter.update_with(|it| it * 2), 2);
+    /// assert_eq!(counter.get(), 2);
+    /// ```
+    fn update_with(&self, function: impl FnOnce(T) -> T) -> T where T: Copy;
+}
+
+impl<T> CellApplyExt<T> for Cell<T> {
+    #[inline(always)]
+    fn update_with(&self, function: impl FnOnce(T) -> T) -> T where T: Copy {
+        let value = function(self.get());
+        self.set(value);
+        value
+    }
+}
+
+pub trait OnceApplyExt<T> {
+    /// Returns the contained value, initializing it first if the cell
+    /// is empty: the value starts out as `T::default()` and is then
+    /// configured by the given function.
+    ///
+    /// Example:
+    /// ```
+    /// # #[cfg(feature = "std")] {
+    /// # use apply_also::OnceApplyExt;
+    /// use std::sync::OnceLock;
+    ///
+    /// #[derive(Default)]
+    /// struct Config { verbose: bool, retries: u32 }
+    ///
+    /// static CONFIG: OnceLock<Config> = OnceLock::new();
+    /// let config = CONFIG.get_or_init_also(|it| {
+    ///     it.verbose = true;
+    ///     it.retries = 3;
+    /// });
+    /// assert_eq!(config.retries, 3);
+    /// # }
+    /// ```
+    fn get_or_init_also(&self, function: impl FnOnce(&mut T)) -> &T where T: Default;
+}
+
+impl<T> OnceApplyExt<T> for OnceCell<T> {
+    #[inline(always)]
+    fn get_or_init_also(&self, function: impl FnOnce(&mut T)) -> &T where T: Default {
+        self.get_or_init(|| init(function))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> OnceApplyExt<T> for OnceLock<T> {
+    #[inline(always)]
+    fn get_or_init_also(&self, function: impl FnOnce(&mut T)) -> &T where T: Default {
+        self.get_or_init(|| init(function))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ CellApplyExt, OnceApplyExt, RefCellApplyExt };
+    use core::cell::{ Cell, OnceCell, RefCell };
+    #[cfg(feature = "std")]
+    use std::sync::OnceLock;
+
+    #[test]
+    fn apply_borrow() {
+        let cell = RefCell::new(vec![1]);
+        cell.also_borrow_mut(|it| it.push(2));
+        assert_eq!(cell.apply_borrow(|it| it.len()), 2);
+
+        let reader = cell.borrow();
+        assert_eq!(cell.try_apply_borrow(|it| it[1]).ok(), Some(2));
+        assert!(cell.try_also_borrow_mut(|it| it.clear()).is_err());
+        drop(reader);
+
+        let writer = cell.borrow_mut();
+        assert!(cell.try_apply_borrow(|it| it.len()).is_err());
+        drop(writer);
+        assert_eq!(*cell.borrow(), vec![1, 2]);
+    }
+
+    #[test]
+    fn update_with() {
+        let cell = Cell::new(3);
+        assert_eq!(cell.update_with(|it| it + 1), 4);
+        assert_eq!(cell.get(), 4);
+    }
+
+    #[test]
+    fn get_or_init_also() {
+        let cell = OnceCell::new();
+        assert_eq!(cell.get_or_init_also(|it: &mut Vec<i32>| it.push(1)), &vec![1]);
+        assert_eq!(cell.get_or_init_also(|_| unreachable!()), &vec![1]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn get_or_init_also_once_lock() {
+        let lock = OnceLock::new();
+        assert_eq!(*lock.get_or_init_also(|it: &mut String| it.push('a')), "a");
+        assert_eq!(*lock.get_or_init_also(|it| it.push('b')), "a");
+    }
+}
@@ -0,0 +1,73 @@
+//! `apply` and `also` variants taking any number of functions.
+
+use crate::ApplyAlso;
+
+pub trait ApplyChain<T> {
+    /// Applies the given functions to this value in order, each to the
+    /// result of the previous one. Returns the last result, or the
+    /// value itself if there are no functions. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyChain;
+    /// let stages: [fn(i32) -> i32; 3] = [|it| it + 1, |it| it * 2, |it| it - 3];
+    /// assert_eq!(5.apply_fold(stages), 9);
+    /// ```
+    fn apply_fold<F: FnOnce(T) -> T>(self, functions: impl IntoIterator<Item = F>) -> T;
+
+    /// Applies the given functions to a reference of this value in
+    /// order. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyChain;
+    /// use core::cell::Cell;
+    ///
+    /// let (logged, counted) = (Cell::new(0), Cell::new(0));
+    /// let observers: [&dyn Fn(&i32); 2] = [&|it| logged.set(*it), &|_| counted.set(counted.get() + 1)];
+    /// let x = 512.also_all(observers);
+    /// assert_eq!(x, 512);
+    /// assert_eq!((logged.get(), counted.get()), (512, 1));
+    /// ```
+    fn also_all<F: FnOnce(&T)>(self, functions: impl IntoIterator<Item = F>) -> T;
+}
+
+impl<T: ApplyAlso> ApplyChain<T> for T {
+    #[inline(always)]
+    fn apply_fold<F: FnOnce(T) -> T>(self, functions: impl IntoIterator<Item = F>) -> T {
+        functions.into_iter().fold(self, |value, function| function(value))
+    }
+
+    #[inline(always)]
+    fn also_all<F: FnOnce(&T)>(self, functions: impl IntoIterator<Item = F>) -> T {
+        for function in functions {
+            function(&self);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_fold() {
+        let stages: Vec<Box<dyn FnOnce(String) -> String>> = vec![
+            Box::new(|it| it.trim().to_string()),
+            Box::new(|it| it.to_uppercase()),
+        ];
+        assert_eq!("  hello ".to_string().apply_fold(stages), "HELLO");
+        assert_eq!(1.apply_fold(Vec::<fn(i32) -> i32>::new()), 1);
+    }
+
+    #[test]
+    fn also_all() {
+        let seen = core::cell::RefCell::new(Vec::new());
+        let log = |it: &&str| seen.borrow_mut().push(format!("log {}", it));
+        let audit = |it: &&str| seen.borrow_mut().push(format!("audit {}", it));
+        let observers: [&dyn Fn(&&str); 2] = [&log, &audit];
+        assert_eq!("x".also_all(observers), "x");
+        assert_eq!(seen.into_inner(), vec!["log x", "audit x"]);
+    }
+}
@@ -0,0 +1,242 @@
+//! `also` variants sending a clone of the value into a channel,
+//! for fanning values out to other tasks.
+//!
+//! Besides the channels of `std`, the `crossbeam` feature adds support
+//! for `crossbeam_channel`, and the `tokio` feature for the channels of
+//! `tokio::sync`.
+
+use crate::ApplyAlso;
+#[cfg(feature = "tokio")]
+use core::future::Future;
+use std::sync::mpsc::{ SendError, Sender, SyncSender };
+#[cfg(feature = "tokio")]
+use tokio::sync::{ broadcast, mpsc };
+
+/// The sending half of a channel, which [`AlsoSend`] can send values into.
+pub trait TeeSender<T> {
+    /// The error returned if the value could not be sent.
+    type Error;
+
+    /// Sends the given value into this channel.
+    fn tee_send(&self, value: T) -> Result<(), Self::Error>;
+}
+
+impl<T> TeeSender<T> for Sender<T> {
+    type Error = SendError<T>;
+
+    #[inline(always)]
+    fn tee_send(&self, value: T) -> Result<(), SendError<T>> {
+        self.send(value)
+    }
+}
+
+/// Blocks while the channel is full.
+impl<T> TeeSender<T> for SyncSender<T> {
+    type Error = SendError<T>;
+
+    #[inline(always)]
+    fn tee_send(&self, value: T) -> Result<(), SendError<T>> {
+        self.send(value)
+    }
+}
+
+/// Blocks while the channel is full.
+#[cfg(feature = "crossbeam")]
+impl<T> TeeSender<T> for crossbeam_channel::Sender<T> {
+    type Error = crossbeam_channel::SendError<T>;
+
+    #[inline(always)]
+    fn tee_send(&self, value: T) -> Result<(), Self::Error> {
+        self.send(value)
+    }
+}
+
+/// Fails if the channel is full, see [`AlsoSendAsync`] for waiting
+/// until there is capacity.
+#[cfg(feature = "tokio")]
+impl<T> TeeSender<T> for mpsc::Sender<T> {
+    type Error = mpsc::error::TrySendError<T>;
+
+    #[inline(always)]
+    fn tee_send(&self, value: T) -> Result<(), Self::Error> {
+        self.try_send(value)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> TeeSender<T> for mpsc::UnboundedSender<T> {
+    type Error = mpsc::error::SendError<T>;
+
+    #[inline(always)]
+    fn tee_send(&self, value: T) -> Result<(), Self::Error> {
+        self.send(value)
+    }
+}
+
+/// Fails if there are no receivers.
+#[cfg(feature = "tokio")]
+impl<T> TeeSender<T> for broadcast::Sender<T> {
+    type Error = broadcast::error::SendError<T>;
+
+    #[inline(always)]
+    fn tee_send(&self, value: T) -> Result<(), Self::Error> {
+        self.send(value).map(drop)
+    }
+}
+
+pub trait AlsoSend<T> {
+    /// Sends a clone of this value into the given channel, ignoring
+    /// whether it could be sent. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, AlsoSend };
+    /// use std::sync::mpsc;
+    ///
+    /// let (audit, received) = mpsc::channel();
+    /// let x = 256.apply(|it| it * 2).also_send(&audit);
+    /// assert_eq!(x, 512);
+    /// assert_eq!(received.recv(), Ok(512));
+    /// ```
+    fn also_send<S: TeeSender<T>>(self, sender: &S) -> T where T: Clone;
+
+    /// Sends a clone of this value into the given channel.
+    /// Returns the original value, or the error if it could not be sent.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoSend;
+    /// use std::sync::mpsc;
+    ///
+    /// let (audit, received) = mpsc::channel();
+    /// drop(received);
+    /// assert!("lost".try_also_send(&audit).is_err());
+    /// ```
+    fn try_also_send<S: TeeSender<T>>(self, sender: &S) -> Result<T, S::Error> where T: Clone;
+}
+
+impl<T: ApplyAlso> AlsoSend<T> for T {
+    #[inline(always)]
+    fn also_send<S: TeeSender<T>>(self, sender: &S) -> T where T: Clone {
+        let _ = sender.tee_send(self.clone());
+        self
+    }
+
+    #[inline(always)]
+    fn try_also_send<S: TeeSender<T>>(self, sender: &S) -> Result<T, S::Error> where T: Clone {
+        sender.tee_send(self.clone())?;
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub trait AlsoSendAsync<T> {
+    /// Sends a clone of this value into the given bounded `tokio`
+    /// channel, waiting until there is capacity, and ignoring whether
+    /// it could be sent. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoSendAsync;
+    /// # async fn run() {
+    /// let (audit, mut received) = tokio::sync::mpsc::channel(1);
+    /// let x = 512.also_send_async(&audit).await;
+    /// assert_eq!(x, 512);
+    /// assert_eq!(received.recv().await, Some(512));
+    /// # }
+    /// ```
+    fn also_send_async(self, sender: &mpsc::Sender<T>) -> impl Future<Output = T> where T: Clone;
+
+    /// Sends a clone of this value into the given bounded `tokio`
+    /// channel, waiting until there is capacity. Returns the original
+    /// value, or the error if the channel is closed.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoSendAsync;
+    /// # async fn run() {
+    /// let (audit, received) = tokio::sync::mpsc::channel(1);
+    /// drop(received);
+    /// assert!("lost".try_also_send_async(&audit).await.is_err());
+    /// # }
+    /// ```
+    fn try_also_send_async(self, sender: &mpsc::Sender<T>) -> impl Future<Output = Result<T, mpsc::error::SendError<T>>> where T: Clone;
+}
+
+#[cfg(feature = "tokio")]
+impl<T: ApplyAlso> AlsoSendAsync<T> for T {
+    #[inline(always)]
+    async fn also_send_async(self, sender: &mpsc::Sender<T>) -> T where T: Clone {
+        let _ = sender.send(self.clone()).await;
+        self
+    }
+
+    #[inline(always)]
+    async fn try_also_send_async(self, sender: &mpsc::Sender<T>) -> Result<T, mpsc::error::SendError<T>> where T: Clone {
+        sender.send(self.clone()).await?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn also_send() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let x = vec![1].also_send(&sender).also_send(&sender);
+        assert_eq!(x, vec![1]);
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![vec![1], vec![1]]);
+
+        drop(receiver);
+        assert_eq!(vec![2].also_send(&sender), vec![2]);
+    }
+
+    #[test]
+    fn try_also_send() {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+        assert_eq!("a".try_also_send(&sender), Ok("a"));
+        assert_eq!(receiver.recv(), Ok("a"));
+
+        drop(receiver);
+        assert_eq!("b".try_also_send(&sender), Err(SendError("b")));
+    }
+
+    #[cfg(feature = "crossbeam")]
+    #[test]
+    fn also_send_crossbeam() {
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        assert_eq!(1.also_send(&sender), 1);
+        assert_eq!(receiver.try_recv(), Ok(1));
+
+        drop(receiver);
+        assert!(2.try_also_send(&sender).is_err());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn also_send_tokio() {
+        let (sender, mut receiver) = broadcast::channel(2);
+        assert_eq!("a".try_also_send(&sender).ok(), Some("a"));
+        assert_eq!(receiver.try_recv(), Ok("a"));
+
+        let (sender, mut receiver) = mpsc::channel(1);
+        assert_eq!(1.also_send(&sender), 1);
+        assert!(2.try_also_send(&sender).is_err());
+        assert_eq!(receiver.try_recv(), Ok(1));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn also_send_async() {
+        use crate::test_util::block_on;
+
+        let (sender, mut receiver) = mpsc::channel(1);
+        assert_eq!(block_on(vec![1].also_send_async(&sender)), vec![1]);
+        assert_eq!(receiver.try_recv(), Ok(vec![1]));
+
+        drop(receiver);
+        assert!(block_on(vec![2].try_also_send_async(&sender)).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+//! Renamed aliases of the core methods, for use alongside crates like
+//! `tap` or `apply`, and types with inherent methods of the same names.
+
+use crate::ApplyAlso;
+
+pub trait ApplyCompat<T> {
+    /// Alias of [`Apply::apply`](crate::Apply::apply).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyCompat;
+    /// let x = 256.k_apply(|it| it * 2);
+    /// assert_eq!(x, 512);
+    /// ```
+    fn k_apply<R>(self, function: impl FnOnce(T) -> R) -> R;
+
+    /// Alias of [`Also::also`](crate::Also::also).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyCompat;
+    /// let mut seen = 0;
+    /// let x = 5.k_also(|it| seen = *it);
+    /// assert_eq!((x, seen), (5, 5));
+    /// ```
+    fn k_also(self, function: impl FnOnce(&T)) -> T;
+
+    /// Alias of [`Also::also`](crate::Also::also), named after the unix
+    /// `tee` command.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyCompat;
+    /// let x = "hello".k_tee(|it| println!("{}", it)).len();
+    /// assert_eq!(x, 5);
+    /// ```
+    fn k_tee(self, function: impl FnOnce(&T)) -> T;
+
+    /// Alias of [`TakeIf::take_if`](crate::TakeIf::take_if), which is
+    /// shadowed by the inherent `Option::take_if`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyCompat;
+    /// let x = Some(3).k_take_if(Option::is_some);
+    /// assert_eq!(x, Some(Some(3)));
+    /// ```
+    fn k_take_if(self, predicate: impl FnOnce(&T) -> bool) -> Option<T>;
+}
+
+impl<T: ApplyAlso> ApplyCompat<T> for T {
+    #[inline(always)]
+    fn k_apply<R>(self, function: impl FnOnce(T) -> R) -> R {
+        function(self)
+    }
+
+    #[inline(always)]
+    fn k_also(self, function: impl FnOnce(&T)) -> T {
+        function(&self);
+        self
+    }
+
+    #[inline(always)]
+    fn k_tee(self, function: impl FnOnce(&T)) -> T {
+        function(&self);
+        self
+    }
+
+    #[inline(always)]
+    fn k_take_if(self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
+        if predicate(&self) {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases() {
+        let mut log = Vec::new();
+        let x = 2.k_apply(|it| it + 1).k_also(|it| log.push(*it)).k_tee(|it| log.push(it * 2));
+        assert_eq!(x, 3);
+        assert_eq!(log, vec![3, 6]);
+        assert_eq!(None::<i32>.k_take_if(Option::is_none), Some(None));
+        assert_eq!(1.k_take_if(|it| *it > 1), None);
+    }
+}
@@ -0,0 +1,33 @@
+//! Composing functions before applying them.
+
+pub trait ThenFn<A, B> {
+    /// Returns a function applying this function, then the given
+    /// function to the result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, ThenFn };
+    /// let parse_and_double = str::parse::<i32>.then_fn(|it| it.map(|it| it * 2));
+    /// assert_eq!("21".apply(parse_and_double), Ok(42));
+    /// ```
+    fn then_fn<C>(self, next: impl FnOnce(B) -> C) -> impl FnOnce(A) -> C;
+}
+
+impl<A, B, F: FnOnce(A) -> B> ThenFn<A, B> for F {
+    #[inline(always)]
+    fn then_fn<C>(self, next: impl FnOnce(B) -> C) -> impl FnOnce(A) -> C {
+        move |value| next(self(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn then_fn() {
+        let name = String::from("x");
+        let greet = (|it: &str| format!("{} {}", it, name)).then_fn(|it| it.to_uppercase()).then_fn(|it| it.len());
+        assert_eq!(greet("hello"), 7);
+    }
+}
@@ -0,0 +1,72 @@
+//! `apply` and `also` variants for `Cow`.
+
+use alloc::borrow::{ Cow, ToOwned };
+
+pub trait CowApplyExt<'a, B: ?Sized + ToOwned + 'a> {
+    /// Applies the given function to the borrowed form of this value
+    /// and returns the result, without taking ownership of the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::CowApplyExt;
+    /// use std::borrow::Cow;
+    ///
+    /// let name: Cow<str> = Cow::Borrowed("alice");
+    /// assert_eq!(name.apply_cow(str::len), 5);
+    /// ```
+    fn apply_cow<R>(self, function: impl FnOnce(&B) -> R) -> R;
+
+    /// Applies the given function to a mutable reference of the owned
+    /// form of this value, cloning the value first if it is borrowed.
+    /// Returns the now owned value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::CowApplyExt;
+    /// use std::borrow::Cow;
+    ///
+    /// fn normalize(input: &str) -> Cow<str> {
+    ///     let value = Cow::Borrowed(input);
+    ///     if input.contains(' ') {
+    ///         value.also_to_mut(|it| it.retain(|c| c != ' '))
+    ///     } else {
+    ///         value
+    ///     }
+    /// }
+    /// assert!(matches!(normalize("ab"), Cow::Borrowed("ab")));
+    /// assert_eq!(normalize("a b"), "ab");
+    /// ```
+    fn also_to_mut(self, function: impl FnOnce(&mut B::Owned)) -> Cow<'a, B>;
+}
+
+impl<'a, B: ?Sized + ToOwned + 'a> CowApplyExt<'a, B> for Cow<'a, B> {
+    #[inline(always)]
+    fn apply_cow<R>(self, function: impl FnOnce(&B) -> R) -> R {
+        function(&self)
+    }
+
+    #[inline(always)]
+    fn also_to_mut(mut self, function: impl FnOnce(&mut B::Owned)) -> Cow<'a, B> {
+        function(self.to_mut());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_cow() {
+        assert_eq!(Cow::Borrowed("abc").apply_cow(str::len), 3);
+        assert_eq!(Cow::<[i32]>::Owned(vec![1, 2]).apply_cow(|it| it[1]), 2);
+    }
+
+    #[test]
+    fn also_to_mut() {
+        let borrowed = [1, 2];
+        let value = Cow::Borrowed(&borrowed[..]).also_to_mut(|it| it.push(3));
+        assert!(matches!(value, Cow::Owned(_)));
+        assert_eq!(*value, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,120 @@
+//! `apply` and `also` variants taking trait objects instead of
+//! generic functions. Every distinct closure passed to `apply` creates
+//! a new monomorphized copy of it; these variants are instantiated only
+//! once per value type, which keeps binary size down on cold paths.
+
+use crate::ApplyAlso;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+pub trait ApplyDyn<T> {
+    /// Applies the given function object to this value and
+    /// returns the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyDyn;
+    /// let x = 256.apply_dyn(&mut |it| it * 2);
+    /// assert_eq!(x, 512);
+    /// ```
+    fn apply_dyn<R>(self, function: &mut dyn FnMut(T) -> R) -> R;
+
+    /// Applies the given boxed function to this value and
+    /// returns the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyDyn;
+    /// let suffix = String::from("!");
+    /// let x = String::from("hi").apply_boxed(Box::new(move |it| it + &suffix));
+    /// assert_eq!(x, "hi!");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn apply_boxed<R>(self, function: Box<dyn FnOnce(T) -> R + '_>) -> R;
+}
+
+pub trait AlsoDyn<T> {
+    /// Applies the given function object to a reference of this value.
+    /// Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoDyn;
+    /// let x = 3.also_dyn(&|it| println!("x = {}", it));
+    /// assert_eq!(x, 3);
+    /// ```
+    fn also_dyn(self, function: &dyn Fn(&T)) -> T;
+
+    /// Applies the given function object to a mutable reference of
+    /// this value. Returns the mutated value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoDyn;
+    /// let x = vec![1].also_mut_dyn(&mut |it| it.push(2));
+    /// assert_eq!(x, vec![1, 2]);
+    /// ```
+    fn also_mut_dyn(self, function: &mut dyn FnMut(&mut T)) -> T;
+}
+
+impl<T: ApplyAlso> ApplyDyn<T> for T {
+    #[inline(always)]
+    fn apply_dyn<R>(self, function: &mut dyn FnMut(T) -> R) -> R {
+        function(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    fn apply_boxed<R>(self, function: Box<dyn FnOnce(T) -> R + '_>) -> R {
+        function(self)
+    }
+}
+
+impl<T: ApplyAlso> AlsoDyn<T> for T {
+    #[inline(always)]
+    fn also_dyn(self, function: &dyn Fn(&T)) -> T {
+        function(&self);
+        self
+    }
+
+    #[inline(always)]
+    fn also_mut_dyn(mut self, function: &mut dyn FnMut(&mut T)) -> T {
+        function(&mut self);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_dyn() {
+        let mut calls = 0;
+        let mut function = |it: i32| {
+            calls += 1;
+            it * 2
+        };
+        assert_eq!(5.apply_dyn(&mut function), 10);
+        assert_eq!(7.apply_dyn(&mut function), 14);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn apply_boxed() {
+        let function: Box<dyn FnOnce(Vec<i32>) -> usize> = Box::new(|it| it.len());
+        assert_eq!(vec![1, 2].apply_boxed(function), 2);
+    }
+
+    #[test]
+    fn also_dyn() {
+        let mut calls = 0;
+        let x = 1.also_mut_dyn(&mut |it| {
+            calls += 1;
+            *it += 1;
+        });
+        assert_eq!((x, calls), (2, 1));
+        assert_eq!("a".also_dyn(&|it| assert_eq!(*it, "a")), "a");
+    }
+}
@@ -0,0 +1,50 @@
+//! `apply` variant running a cleanup function, even if the function panics.
+
+use crate::ApplyAlso;
+use crate::guard::defer;
+
+pub trait ApplyEnsuring<T> {
+    /// Applies the given function to this value and returns the result,
+    /// then runs the given cleanup function. The cleanup function also
+    /// runs if the function panics, like a `finally` block.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyEnsuring;
+    /// use core::cell::Cell;
+    ///
+    /// let open = Cell::new(true);
+    /// let x = 256.apply_ensuring(|it| it * 2, || open.set(false));
+    /// assert_eq!(x, 512);
+    /// assert!(!open.get());
+    /// ```
+    fn apply_ensuring<R>(self, function: impl FnOnce(T) -> R, cleanup: impl FnOnce()) -> R;
+}
+
+impl<T: ApplyAlso> ApplyEnsuring<T> for T {
+    #[inline(always)]
+    fn apply_ensuring<R>(self, function: impl FnOnce(T) -> R, cleanup: impl FnOnce()) -> R {
+        let _guard = defer(cleanup);
+        function(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+    use std::panic::{ self, AssertUnwindSafe };
+
+    #[test]
+    fn apply_ensuring() {
+        let cleaned = Cell::new(0);
+        assert_eq!(2.apply_ensuring(|it| it + 1, || cleaned.set(cleaned.get() + 1)), 3);
+        assert_eq!(cleaned.get(), 1);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            2.apply_ensuring(|_| -> i32 { panic!("failed") }, || cleaned.set(cleaned.get() + 1))
+        }));
+        assert!(result.is_err());
+        assert_eq!(cleaned.get(), 2);
+    }
+}
@@ -0,0 +1,85 @@
+//! Free function mirrors of `apply` and `also`, returning closures that
+//! can be passed to iterator adapters directly.
+//!
+//! ```
+//! use apply_also::fns::{ also_fn, also_mut_fn };
+//!
+//! let mut seen = Vec::new();
+//! let sorted: Vec<Vec<i32>> = vec![vec![2, 1], vec![4, 3]]
+//!     .into_iter()
+//!     .map(also_fn(|it: &Vec<i32>| seen.push(it.len())))
+//!     .map(also_mut_fn(|it: &mut Vec<i32>| it.sort()))
+//!     .collect();
+//! assert_eq!(sorted, vec![vec![1, 2], vec![3, 4]]);
+//! assert_eq!(seen, vec![2, 2]);
+//! ```
+
+/// Returns a function applying the given function to its argument and
+/// returning the result, like [`Apply::apply`](crate::Apply::apply).
+///
+/// Example:
+/// ```
+/// # use apply_also::fns::apply_fn;
+/// let x: Vec<usize> = vec!["a", "bc"].into_iter().map(apply_fn(str::len)).collect();
+/// assert_eq!(x, vec![1, 2]);
+/// ```
+#[inline(always)]
+pub fn apply_fn<T, R>(mut function: impl FnMut(T) -> R) -> impl FnMut(T) -> R {
+    move |value| function(value)
+}
+
+/// Returns a function applying the given function to a reference of
+/// its argument and returning the argument, like
+/// [`Also::also`](crate::Also::also).
+///
+/// Example:
+/// ```
+/// # use apply_also::fns::also_fn;
+/// let mut count = 0;
+/// let x: Vec<i32> = (1..=3).map(also_fn(|_: &i32| count += 1)).collect();
+/// assert_eq!((x, count), (vec![1, 2, 3], 3));
+/// ```
+#[inline(always)]
+pub fn also_fn<T>(mut function: impl FnMut(&T)) -> impl FnMut(T) -> T {
+    move |value| {
+        function(&value);
+        value
+    }
+}
+
+/// Returns a function applying the given function to a mutable
+/// reference of its argument and returning the argument, like
+/// [`Also::also_mut`](crate::Also::also_mut).
+///
+/// Example:
+/// ```
+/// # use apply_also::fns::also_mut_fn;
+/// let x: Vec<i32> = (1..=3).map(also_mut_fn(|it: &mut i32| *it *= 2)).collect();
+/// assert_eq!(x, vec![2, 4, 6]);
+/// ```
+#[inline(always)]
+pub fn also_mut_fn<T>(mut function: impl FnMut(&mut T)) -> impl FnMut(T) -> T {
+    move |mut value| {
+        function(&mut value);
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fns() {
+        let mut log = Vec::new();
+        let x: Vec<String> = vec![3, 1]
+            .into_iter()
+            .map(also_fn(|it: &i32| log.push(*it)))
+            .map(also_mut_fn(|it: &mut i32| *it += 1))
+            .map(apply_fn(|it: i32| it.to_string()))
+            .collect();
+        assert_eq!(x, vec!["4", "2"]);
+        assert_eq!(log, vec![3, 1]);
+        assert_eq!(Some(5).map(also_mut_fn(|it: &mut i32| *it = -*it)), Some(-5));
+    }
+}
@@ -0,0 +1,178 @@
+//! Asynchronous variants of `apply` and `also`, where the given
+//! function returns a future, and (with the `futures` feature)
+//! extensions applying functions to the output of a future.
+
+use crate::ApplyAlso;
+use core::future::Future;
+
+pub trait ApplyAsync<T, R>: ApplyAlso {
+    /// Applies the given function to this value and returns the
+    /// future it returns, resolving to the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyAsync;
+    /// # async fn lookup(id: u32) -> String { format!("user-{}", id) }
+    /// # async fn run() {
+    /// let name = 7.apply_async(lookup).await;
+    /// assert_eq!(name, "user-7");
+    /// # }
+    /// ```
+    fn apply_async<Fut: Future<Output = R>>(self, function: impl FnOnce(T) -> Fut) -> Fut;
+}
+
+pub trait AlsoAsync<T>: ApplyAlso {
+    /// Applies the given function to a reference of this value.
+    /// Returns the original value once the returned future resolves.
+    ///
+    /// The future can not borrow the value, so copy what it needs out
+    /// of the reference.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoAsync;
+    /// # async fn audit(id: u32) {}
+    /// # async fn run() {
+    /// let x = 3.also_async(|it| audit(*it)).await;
+    /// assert_eq!(x, 3);
+    /// # }
+    /// ```
+    fn also_async<Fut: Future<Output = ()>>(self, function: impl FnOnce(&T) -> Fut) -> impl Future<Output = T>;
+
+    /// Applies the given function to a mutable reference of this
+    /// value. Returns the mutated value once the returned future
+    /// resolves.
+    ///
+    /// The future can not borrow the value, so the mutation happens
+    /// before the future is awaited.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoAsync;
+    /// # async fn notify(len: usize) {}
+    /// # async fn run() {
+    /// let x = vec!["hello"].also_mut_async(|it| {
+    ///     it.push("world");
+    ///     notify(it.len())
+    /// }).await;
+    /// assert_eq!(x, vec!["hello", "world"]);
+    /// # }
+    /// ```
+    fn also_mut_async<Fut: Future<Output = ()>>(self, function: impl FnOnce(&mut T) -> Fut) -> impl Future<Output = T>;
+}
+
+#[cfg(feature = "futures")]
+pub trait FutureApplyExt: Future + Sized {
+    /// Applies the given function to the output of this future
+    /// once it resolves and returns the result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::FutureApplyExt;
+    /// # async fn run() {
+    /// let x = async { 256 }.then_apply(|it| it * 2).await;
+    /// assert_eq!(x, 512);
+    /// # }
+    /// ```
+    fn then_apply<R>(self, function: impl FnOnce(Self::Output) -> R) -> impl Future<Output = R>;
+
+    /// Applies the given function to a reference of the output of this
+    /// future once it resolves. Returns the original output.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::FutureApplyExt;
+    /// # async fn run() {
+    /// let x = async { 3 }.then_also(|it| {
+    ///     println!("resolved to {}", it);
+    /// }).await;
+    /// assert_eq!(x, 3);
+    /// # }
+    /// ```
+    fn then_also(self, function: impl FnOnce(&Self::Output)) -> impl Future<Output = Self::Output>;
+}
+
+impl<T: ApplyAlso, R> ApplyAsync<T, R> for T {
+    #[inline(always)]
+    fn apply_async<Fut: Future<Output = R>>(self, function: impl FnOnce(T) -> Fut) -> Fut {
+        function(self)
+    }
+}
+
+impl<T: ApplyAlso> AlsoAsync<T> for T {
+    fn also_async<Fut: Future<Output = ()>>(self, function: impl FnOnce(&T) -> Fut) -> impl Future<Output = T> {
+        let future = function(&self);
+        async move {
+            future.await;
+            self
+        }
+    }
+
+    fn also_mut_async<Fut: Future<Output = ()>>(mut self, function: impl FnOnce(&mut T) -> Fut) -> impl Future<Output = T> {
+        let future = function(&mut self);
+        async move {
+            future.await;
+            self
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<F: Future> FutureApplyExt for F {
+    async fn then_apply<R>(self, function: impl FnOnce(Self::Output) -> R) -> R {
+        function(self.await)
+    }
+
+    async fn then_also(self, function: impl FnOnce(&Self::Output)) -> Self::Output {
+        let output = self.await;
+        function(&output);
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::block_on;
+
+    async fn double(x: i32) -> i32 {
+        x * 2
+    }
+
+    #[test]
+    fn apply_async() {
+        let x = block_on(21.apply_async(double));
+        assert_eq!(x, 42);
+    }
+
+    #[test]
+    fn also_async() {
+        let x = block_on(vec![1, 2].also_mut_async(|it| {
+            it.push(3);
+            let last = it[2];
+            async move {
+                assert_eq!(double(last).await, 6);
+            }
+        }));
+        assert_eq!(x, vec![1, 2, 3]);
+
+        let seen = std::cell::Cell::new(0);
+        let y = block_on(5.also_async(|it| {
+            let it = *it;
+            let seen = &seen;
+            async move { seen.set(double(it).await) }
+        }));
+        assert_eq!((y, seen.get()), (5, 10));
+    }
+
+    #[test]
+    #[cfg(feature = "futures")]
+    fn then_apply() {
+        let x = block_on(double(4).then_apply(|it| it + 1));
+        assert_eq!(x, 9);
+
+        let mut seen = 0;
+        let y = block_on(double(4).then_also(|it| seen = *it));
+        assert_eq!((y, seen), (8, 8));
+    }
+}
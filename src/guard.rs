@@ -0,0 +1,236 @@
+//! Scope guards running a function when they go out of scope.
+//!
+//! ```
+//! use apply_also::Also;
+//! use apply_also::guard::guard;
+//!
+//! let mut log = Vec::new();
+//! {
+//!     let mut events = guard(Vec::new(), |it| log.extend(it)).also_mut(|it| it.push("setup"));
+//!     events.push("work");
+//! }
+//! assert_eq!(log, vec!["setup", "work"]);
+//! ```
+
+use crate::ApplyAlso;
+use core::fmt;
+use core::mem::ManuallyDrop;
+use core::ops::{ Deref, DerefMut };
+use core::ptr;
+
+/// A value paired with a function that is called with the value
+/// when the guard is dropped, created by [`guard`] or [`defer`].
+///
+/// The guard dereferences to the value. [`ScopeGuard::into_inner`]
+/// dismisses the guard without calling the function.
+#[must_use = "the function runs immediately if the guard is not bound to a variable"]
+pub struct ScopeGuard<T, F: FnOnce(T)> {
+    value: ManuallyDrop<T>,
+    on_drop: ManuallyDrop<F>,
+}
+
+/// Creates a guard that calls `on_drop` with the given value when dropped.
+///
+/// Example:
+/// ```
+/// # use apply_also::guard::guard;
+/// use core::cell::Cell;
+///
+/// let closed = Cell::new(false);
+/// {
+///     let file = guard("data.txt", |_| closed.set(true));
+///     assert_eq!(*file, "data.txt");
+/// }
+/// assert!(closed.get());
+/// ```
+#[inline(always)]
+pub fn guard<T, F: FnOnce(T)>(value: T, on_drop: F) -> ScopeGuard<T, F> {
+    ScopeGuard { value: ManuallyDrop::new(value), on_drop: ManuallyDrop::new(on_drop) }
+}
+
+/// Creates a guard that calls the given function when dropped.
+///
+/// Example:
+/// ```
+/// # use apply_also::guard::defer;
+/// use core::cell::Cell;
+///
+/// let done = Cell::new(false);
+/// {
+///     let _guard = defer(|| done.set(true));
+///     assert!(!done.get());
+/// }
+/// assert!(done.get());
+/// ```
+#[inline(always)]
+pub fn defer(function: impl FnOnce()) -> ScopeGuard<(), impl FnOnce(())> {
+    guard((), move |()| function())
+}
+
+impl<T, F: FnOnce(T)> ScopeGuard<T, F> {
+    /// Dismisses this guard without calling its function and
+    /// returns the value. This is an associated function, so that it
+    /// does not shadow methods of the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::guard::{ guard, ScopeGuard };
+    /// let x = guard(5, |_| unreachable!());
+    /// assert_eq!(ScopeGuard::into_inner(x), 5);
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(guard: Self) -> T {
+        let mut guard = ManuallyDrop::new(guard);
+        // SAFETY: `guard` is never used or dropped again, so both fields
+        // are read exactly once.
+        unsafe {
+            ManuallyDrop::drop(&mut guard.on_drop);
+            ptr::read(&*guard.value)
+        }
+    }
+}
+
+impl<T, F: FnOnce(T)> Deref for ScopeGuard<T, F> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, F: FnOnce(T)> DerefMut for ScopeGuard<T, F> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T, F: FnOnce(T)> Drop for ScopeGuard<T, F> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // SAFETY: the fields are taken exactly once, here or in `into_inner`.
+        let (value, on_drop) = unsafe { (ManuallyDrop::take(&mut self.value), ManuallyDrop::take(&mut self.on_drop)) };
+        on_drop(value);
+    }
+}
+
+impl<T: fmt::Debug, F: FnOnce(T)> fmt::Debug for ScopeGuard<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopeGuard").field("value", &*self.value).finish()
+    }
+}
+
+/// A value that calls a function with a mutable reference to itself
+/// when dropped, created by [`AlsoOnDrop::also_on_drop`].
+///
+/// `OnDrop` dereferences to the value.
+#[must_use = "the function runs immediately if the wrapper is not bound to a variable"]
+pub struct OnDrop<T, F: FnOnce(&mut T)> {
+    value: T,
+    on_drop: Option<F>,
+}
+
+impl<T, F: FnOnce(&mut T)> Deref for OnDrop<T, F> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, F: FnOnce(&mut T)> DerefMut for OnDrop<T, F> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T, F: FnOnce(&mut T)> Drop for OnDrop<T, F> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        if let Some(on_drop) = self.on_drop.take() {
+            on_drop(&mut self.value);
+        }
+    }
+}
+
+impl<T: fmt::Debug, F: FnOnce(&mut T)> fmt::Debug for OnDrop<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnDrop").field("value", &self.value).finish()
+    }
+}
+
+pub trait AlsoOnDrop<T> {
+    /// Wraps this value, so that the given function is applied to a
+    /// mutable reference of it when the wrapper is dropped.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoOnDrop;
+    /// let mut flushed = Vec::new();
+    /// {
+    ///     let mut buffer = Vec::new().also_on_drop(|it| flushed.append(it));
+    ///     buffer.push(1);
+    ///     buffer.push(2);
+    /// }
+    /// assert_eq!(flushed, vec![1, 2]);
+    /// ```
+    fn also_on_drop<F: FnOnce(&mut T)>(self, function: F) -> OnDrop<T, F>;
+}
+
+impl<T: ApplyAlso> AlsoOnDrop<T> for T {
+    #[inline(always)]
+    fn also_on_drop<F: FnOnce(&mut T)>(self, function: F) -> OnDrop<T, F> {
+        OnDrop { value: self, on_drop: Some(function) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+    use std::panic::{ self, AssertUnwindSafe };
+
+    #[test]
+    fn guard() {
+        let dropped = Cell::new(0);
+        {
+            let mut x = super::guard(1, |it| dropped.set(it));
+            *x += 1;
+            assert_eq!(dropped.get(), 0);
+        }
+        assert_eq!(dropped.get(), 2);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _x = super::guard(3, |it| dropped.set(it));
+            panic!("failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(dropped.get(), 3);
+    }
+
+    #[test]
+    fn into_inner() {
+        let called = Cell::new(false);
+        let x = super::guard(vec![1], |_| called.set(true));
+        assert_eq!(ScopeGuard::into_inner(x), vec![1]);
+        assert!(!called.get());
+
+        drop(defer(|| called.set(true)));
+        assert!(called.get());
+    }
+
+    #[test]
+    fn also_on_drop() {
+        let dropped = Cell::new(0);
+        {
+            let mut x = 1.also_on_drop(|it| dropped.set(*it));
+            *x += 1;
+            assert_eq!(*x, 2);
+            assert_eq!(dropped.get(), 0);
+        }
+        assert_eq!(dropped.get(), 2);
+    }
+}
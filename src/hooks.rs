@@ -0,0 +1,156 @@
+//! A global registry of hooks, which observe every value passing
+//! through [`also_hooked`](AlsoHooked::also_hooked).
+//!
+//! Hooks receive the value type-erased, together with the location of
+//! the call, and can downcast it to the types they are interested in.
+//! This allows tracing values across an application without touching
+//! each call site.
+//!
+//! ```
+//! use apply_also::{ Apply, AlsoHooked };
+//! use apply_also::hooks;
+//! use core::any::Any;
+//! use core::panic::Location;
+//!
+//! fn trace(value: &dyn Any, location: &'static Location<'static>) {
+//!     if let Some(value) = value.downcast_ref::<i32>() {
+//!         eprintln!("[{}] {}", location, value);
+//!     }
+//! }
+//!
+//! let id = hooks::register(trace);
+//! let x = 256.apply(|it| it * 2).also_hooked();
+//! assert_eq!(x, 512);
+//! hooks::unregister(id);
+//! ```
+
+use crate::ApplyAlso;
+use core::any::Any;
+use core::panic::Location;
+use core::sync::atomic::{ AtomicBool, AtomicUsize, Ordering };
+use std::sync::{ Arc, LazyLock, PoisonError, RwLock };
+
+/// A hook, called with the value and the location of the call.
+pub type Hook = Arc<dyn Fn(&dyn Any, &'static Location<'static>) + Send + Sync>;
+
+/// Identifies a registered hook, returned by [`register`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HookId(usize);
+
+// Copied on write, so that `also_hooked` can call the hooks on a
+// snapshot without holding the lock.
+type Registry = Arc<Vec<(HookId, Hook)>>;
+
+static HOOKS: LazyLock<RwLock<Registry>> = LazyLock::new(Default::default);
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Registers the given hook, which is called on every subsequent
+/// `also_hooked` call, after the hooks registered before it.
+///
+/// Hooks may register and unregister hooks themselves. Calls that
+/// already started are not affected.
+pub fn register(hook: impl Fn(&dyn Any, &'static Location<'static>) + Send + Sync + 'static) -> HookId {
+    let id = HookId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let mut hooks = HOOKS.write().unwrap_or_else(PoisonError::into_inner);
+    Arc::make_mut(&mut hooks).push((id, Arc::new(hook)));
+    id
+}
+
+/// Unregisters the hook with the given id. Returns whether it was
+/// still registered.
+pub fn unregister(id: HookId) -> bool {
+    let mut hooks = HOOKS.write().unwrap_or_else(PoisonError::into_inner);
+    let len = hooks.len();
+    Arc::make_mut(&mut hooks).retain(|(registered, _)| *registered != id);
+    hooks.len() != len
+}
+
+/// Enables or disables all hooks, without unregistering them.
+/// Hooks are enabled by default.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether hooks are enabled.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub trait AlsoHooked<T> {
+    /// Passes a reference of this value to all registered hooks,
+    /// if hooks are enabled. Returns the original value.
+    fn also_hooked(self) -> T where T: Any;
+}
+
+impl<T: ApplyAlso> AlsoHooked<T> for T {
+    #[inline(always)]
+    #[track_caller]
+    fn also_hooked(self) -> T where T: Any {
+        if is_enabled() {
+            let location = Location::caller();
+            let hooks = Arc::clone(&HOOKS.read().unwrap_or_else(PoisonError::into_inner));
+            for (_, hook) in hooks.iter() {
+                hook(&self, location);
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    static SEEN: Mutex<Vec<(u64, u32)>> = Mutex::new(Vec::new());
+
+    fn record(value: &dyn Any, location: &'static Location<'static>) {
+        if let Some(value) = value.downcast_ref::<u64>() {
+            SEEN.lock().unwrap().push((*value, location.line()));
+        }
+    }
+
+    fn record_twice(value: &dyn Any, _: &'static Location<'static>) {
+        if let Some(value) = value.downcast_ref::<u64>() {
+            SEEN.lock().unwrap().push((*value * 2, 0));
+        }
+    }
+
+    // A single test, as the registry is global
+    #[test]
+    fn also_hooked() {
+        let first = register(record);
+        let second = register(record_twice);
+        let (x, line) = (21u64.also_hooked(), line!());
+        assert_eq!(x, 21);
+        assert_eq!("ignored".also_hooked(), "ignored");
+        assert_eq!(*SEEN.lock().unwrap(), vec![(21, line), (42, 0)]);
+
+        set_enabled(false);
+        assert!(!is_enabled());
+        1u64.also_hooked();
+        set_enabled(true);
+
+        assert!(unregister(second));
+        assert!(!unregister(second));
+        let (_, second_line) = (2u64.also_hooked(), line!());
+        assert!(unregister(first));
+        3u64.also_hooked();
+        assert_eq!(SEEN.lock().unwrap()[2..], [(2, second_line)]);
+
+        // Hooks registering and unregistering hooks do not deadlock.
+        let nested = register(|value, _| {
+            if value.downcast_ref::<u64>() == Some(&4) {
+                let inner = register(record);
+                assert!(unregister(inner));
+                register(record_twice);
+            }
+        });
+        4u64.also_hooked();
+        assert_eq!(SEEN.lock().unwrap().len(), 3);
+        5u64.also_hooked();
+        assert_eq!(SEEN.lock().unwrap()[3..], [(10, 0)]);
+        assert!(unregister(nested));
+    }
+}
@@ -0,0 +1,132 @@
+//! Constructor helpers built on `also_mut`.
+
+use core::mem::MaybeUninit;
+use core::ptr;
+
+/// Creates a default value and applies the given function to a
+/// mutable reference of it. Returns the initialized value.
+///
+/// This is a shorthand for `T::default().also_mut(function)`.
+///
+/// Example:
+/// ```
+/// # use apply_also::init;
+/// #[derive(Default)]
+/// struct Config { retries: u32, verbose: bool }
+///
+/// let config = init::<Config>(|it| {
+///     it.retries = 3;
+/// });
+/// assert_eq!(config.retries, 3);
+/// assert!(!config.verbose);
+/// ```
+#[inline(always)]
+pub fn init<T: Default>(function: impl FnOnce(&mut T)) -> T {
+    init_with(T::default, function)
+}
+
+/// Creates a value using the given constructor and applies the
+/// given function to a mutable reference of it. Returns the
+/// initialized value.
+///
+/// Example:
+/// ```
+/// # use apply_also::init_with;
+/// let buffer = init_with(|| Vec::with_capacity(16), |it| {
+///     it.extend_from_slice(b"hello");
+/// });
+/// assert_eq!(buffer, b"hello");
+/// assert!(buffer.capacity() >= 16);
+/// ```
+#[inline(always)]
+pub fn init_with<T>(constructor: impl FnOnce() -> T, function: impl FnOnce(&mut T)) -> T {
+    let mut value = constructor();
+    function(&mut value);
+    value
+}
+
+/// Creates an uninitialized value and applies the given function to a
+/// mutable reference of it. Returns the initialized value.
+///
+/// # Safety
+///
+/// The function has to fully initialize the value, as with
+/// `MaybeUninit::assume_init`.
+///
+/// Example:
+/// ```
+/// # use apply_also::apply_init;
+/// let x: [u8; 4] = unsafe {
+///     apply_init(|it| {
+///         it.write(*b"abcd");
+///     })
+/// };
+/// assert_eq!(&x, b"abcd");
+/// ```
+#[inline(always)]
+pub unsafe fn apply_init<T>(function: impl FnOnce(&mut MaybeUninit<T>)) -> T {
+    let mut value = MaybeUninit::uninit();
+    function(&mut value);
+    value.assume_init()
+}
+
+/// Creates an uninitialized array and applies the given function to
+/// the index and a mutable reference of every element, in order.
+/// Returns the initialized array.
+///
+/// If the function panics, the elements initialized so far are leaked.
+///
+/// # Safety
+///
+/// Every call of the function has to fully initialize the element,
+/// as with `MaybeUninit::assume_init`.
+///
+/// Example:
+/// ```
+/// # use apply_also::init_array;
+/// let squares: [u32; 4] = unsafe {
+///     init_array(|index, it| {
+///         it.write(index as u32 * index as u32);
+///     })
+/// };
+/// assert_eq!(squares, [0, 1, 4, 9]);
+/// ```
+#[inline(always)]
+pub unsafe fn init_array<T, const N: usize>(mut function: impl FnMut(usize, &mut MaybeUninit<T>)) -> [T; N] {
+    let mut array: [MaybeUninit<T>; N] = MaybeUninit::uninit().assume_init();
+    for (index, element) in array.iter_mut().enumerate() {
+        function(index, element);
+    }
+    // SAFETY: every element was initialized by the caller's function,
+    // and `MaybeUninit<T>` has the same layout as `T`.
+    ptr::read(array.as_ptr() as *const [T; N])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_default() {
+        let x = init::<Vec<i32>>(|it| it.push(1));
+        assert_eq!(x, vec![1]);
+
+        let y = init(|it: &mut String| it.push('a'));
+        assert_eq!(y, "a");
+    }
+
+    #[test]
+    fn init_with_constructor() {
+        let x = init_with(|| 5, |it| *it += 1);
+        assert_eq!(x, 6);
+    }
+
+    #[test]
+    fn apply_init() {
+        let x: u64 = unsafe { super::apply_init(|it| { it.write(7); }) };
+        assert_eq!(x, 7);
+
+        let strings: [String; 3] = unsafe { init_array(|index, it| { it.write(index.to_string()); }) };
+        assert_eq!(strings, ["0", "1", "2"]);
+    }
+}
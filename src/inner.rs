@@ -0,0 +1,142 @@
+//! Applying functions to the values inside of a container.
+
+#[cfg(feature = "alloc")]
+use alloc::{ boxed::Box, vec::Vec };
+
+pub trait ApplyInner<T> {
+    /// The type of this container holding values of type `U`.
+    type Output<U>;
+
+    /// Applies the given function to every value contained in this
+    /// container and returns a container of the same shape holding
+    /// the results. Consumes the container.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyInner;
+    /// assert_eq!(Some(2).apply_inner(|it| it * 2), Some(4));
+    /// assert_eq!(Ok::<_, ()>(2).apply_inner(|it| it * 2), Ok(4));
+    /// # #[cfg(feature = "alloc")]
+    /// assert_eq!(vec![1, 2].apply_inner(|it| it * 2), vec![2, 4]);
+    /// ```
+    fn apply_inner<U>(self, function: impl FnMut(T) -> U) -> Self::Output<U>;
+}
+
+impl<T> ApplyInner<T> for Option<T> {
+    type Output<U> = Option<U>;
+
+    #[inline(always)]
+    fn apply_inner<U>(self, function: impl FnMut(T) -> U) -> Option<U> {
+        self.map(function)
+    }
+}
+
+impl<T, E> ApplyInner<T> for Result<T, E> {
+    type Output<U> = Result<U, E>;
+
+    #[inline(always)]
+    fn apply_inner<U>(self, function: impl FnMut(T) -> U) -> Result<U, E> {
+        self.map(function)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ApplyInner<T> for Vec<T> {
+    type Output<U> = Vec<U>;
+
+    #[inline(always)]
+    fn apply_inner<U>(self, function: impl FnMut(T) -> U) -> Vec<U> {
+        self.into_iter().map(function).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ApplyInner<T> for Box<T> {
+    type Output<U> = Box<U>;
+
+    #[inline(always)]
+    fn apply_inner<U>(self, mut function: impl FnMut(T) -> U) -> Box<U> {
+        Box::new(function(*self))
+    }
+}
+
+impl<T, const N: usize> ApplyInner<T> for [T; N] {
+    type Output<U> = [U; N];
+
+    #[inline(always)]
+    fn apply_inner<U>(self, function: impl FnMut(T) -> U) -> [U; N] {
+        self.map(function)
+    }
+}
+
+pub trait ApplyEach<T> {
+    /// Replaces every value contained in this container with the
+    /// result of applying the given function to it, and returns the
+    /// container. Unlike [`ApplyInner::apply_inner`], the type of the
+    /// values is kept, so a `Vec` reuses its allocation.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyEach;
+    /// assert_eq!([1, 2, 3].apply_each(|it| it * 2), [2, 4, 6]);
+    /// # #[cfg(feature = "alloc")]
+    /// assert_eq!(vec!["a", "b"].apply_each(str::trim), vec!["a", "b"]);
+    /// ```
+    fn apply_each(self, function: impl FnMut(T) -> T) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ApplyEach<T> for Vec<T> {
+    #[inline(always)]
+    fn apply_each(self, function: impl FnMut(T) -> T) -> Vec<T> {
+        // collecting a `vec::IntoIter` of the same type reuses its allocation
+        self.into_iter().map(function).collect()
+    }
+}
+
+impl<T, const N: usize> ApplyEach<T> for [T; N] {
+    #[inline(always)]
+    fn apply_each(self, function: impl FnMut(T) -> T) -> [T; N] {
+        self.map(function)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lengths<C: ApplyInner<&'static str>>(container: C) -> C::Output<usize> {
+        container.apply_inner(str::len)
+    }
+
+    #[test]
+    fn apply_inner() {
+        assert_eq!(lengths(Some("abc")), Some(3));
+        assert_eq!(lengths(Err::<&str, _>(1)), Err(1));
+        assert_eq!(lengths(["a", "", "abc"]), [1, 0, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn apply_inner_alloc() {
+        assert_eq!(lengths(vec!["a", "bc"]), vec![1, 2]);
+        assert_eq!(lengths(Box::new("ab")), Box::new(2));
+    }
+
+    #[test]
+    fn apply_each() {
+        assert_eq!(["a", "b"].apply_each(|it| if it == "a" { "c" } else { it }), ["c", "b"]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn apply_each_alloc() {
+        use crate::Also;
+
+        let values = Vec::with_capacity(8).also_mut(|it: &mut Vec<u32>| it.extend([1, 2, 3].iter()));
+        let pointer = values.as_ptr();
+        let doubled = values.apply_each(|it| it * 2);
+        assert_eq!(doubled, vec![2, 4, 6]);
+        assert_eq!(doubled.as_ptr(), pointer);
+    }
+}
@@ -0,0 +1,150 @@
+//! `apply` and `also` variants for iterators.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::Inspect;
+
+pub trait IteratorAlsoExt: Iterator + Sized {
+    /// Applies the given function to a reference of every item of this
+    /// iterator. The items are passed on unchanged.
+    ///
+    /// This is `Iterator::inspect` under the crate's naming.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::IteratorAlsoExt;
+    /// let mut seen = Vec::new();
+    /// let doubled: Vec<_> = [1, 2, 3].iter()
+    ///     .also_each(|it| seen.push(**it))
+    ///     .map(|it| it * 2)
+    ///     .collect();
+    /// assert_eq!(doubled, vec![2, 4, 6]);
+    /// assert_eq!(seen, vec![1, 2, 3]);
+    /// ```
+    fn also_each<F: FnMut(&Self::Item)>(self, function: F) -> Inspect<Self, F>;
+
+    /// Applies the given function to every item of this iterator of
+    /// mutable references. The references are passed on.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::IteratorAlsoExt;
+    /// let mut values = vec![1, 2, 3];
+    /// let count = values.iter_mut().also_each_mut(|it| *it *= 10).count();
+    /// assert_eq!(count, 3);
+    /// assert_eq!(values, vec![10, 20, 30]);
+    /// ```
+    fn also_each_mut<'a, U: ?Sized + 'a>(self, function: impl FnMut(&mut U)) -> impl Iterator<Item = &'a mut U>
+    where
+        Self: Iterator<Item = &'a mut U>;
+
+    /// Applies the given function to this whole iterator and returns
+    /// the result, which allows reusable adapter chains to be applied
+    /// in the middle of a chain.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::IteratorAlsoExt;
+    /// fn evens(it: impl Iterator<Item = u32>) -> impl Iterator<Item = u32> {
+    ///     it.filter(|it| it % 2 == 0)
+    /// }
+    ///
+    /// let x: u32 = (1..=6).apply_pipeline(evens).map(|it| it * 10).sum();
+    /// assert_eq!(x, 120);
+    /// ```
+    fn apply_pipeline<R>(self, function: impl FnOnce(Self) -> R) -> R;
+
+    /// Pushes a clone of every item of this iterator into the given
+    /// vector. The items are passed on unchanged, so this records a
+    /// snapshot of what a chain yields at this point.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::IteratorAlsoExt;
+    /// let mut filtered = Vec::new();
+    /// let sum: i32 = (1..=6)
+    ///     .filter(|it| it % 2 == 0)
+    ///     .also_collect_into(&mut filtered)
+    ///     .map(|it| it * 10)
+    ///     .sum();
+    /// assert_eq!(sum, 120);
+    /// assert_eq!(filtered, vec![2, 4, 6]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn also_collect_into(self, sink: &mut Vec<Self::Item>) -> impl Iterator<Item = Self::Item>
+    where
+        Self::Item: Clone;
+}
+
+impl<I: Iterator> IteratorAlsoExt for I {
+    #[inline(always)]
+    fn also_each<F: FnMut(&Self::Item)>(self, function: F) -> Inspect<Self, F> {
+        self.inspect(function)
+    }
+
+    #[inline(always)]
+    fn also_each_mut<'a, U: ?Sized + 'a>(self, mut function: impl FnMut(&mut U)) -> impl Iterator<Item = &'a mut U>
+    where
+        Self: Iterator<Item = &'a mut U>,
+    {
+        // `inspect` only hands out `&&mut U`, which can not be mutated through.
+        #[allow(clippy::manual_inspect)]
+        self.map(move |it| {
+            function(it);
+            it
+        })
+    }
+
+    #[inline(always)]
+    fn apply_pipeline<R>(self, function: impl FnOnce(Self) -> R) -> R {
+        function(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    fn also_collect_into(self, sink: &mut Vec<Self::Item>) -> impl Iterator<Item = Self::Item>
+    where
+        Self::Item: Clone,
+    {
+        self.inspect(move |it| sink.push(it.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn also_each() {
+        let mut seen = 0;
+        let sum: i32 = (1..=4).also_each(|it| seen += it).sum();
+        assert_eq!((sum, seen), (10, 10));
+    }
+
+    #[test]
+    fn also_each_mut() {
+        let mut words = vec![String::from("a"), String::from("b")];
+        let lengths: Vec<_> = words.iter_mut().also_each_mut(|it| it.push('!')).map(|it| it.len()).collect();
+        assert_eq!(lengths, vec![2, 2]);
+        assert_eq!(words, vec!["a!", "b!"]);
+    }
+
+    #[test]
+    fn apply_pipeline() {
+        let x: Vec<_> = (0..10).apply_pipeline(|it| it.skip(2).step_by(3)).collect();
+        assert_eq!(x, vec![2, 5, 8]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn also_collect_into() {
+        let mut upper = Vec::new();
+        let x: Vec<_> = ["a", "b"].iter().map(|it| it.to_uppercase()).also_collect_into(&mut upper).collect();
+        assert_eq!(x, upper);
+
+        let mut snapshot = vec![0];
+        let last = (1..4).also_collect_into(&mut snapshot).last();
+        assert_eq!(last, Some(3));
+        assert_eq!(snapshot, vec![0, 1, 2, 3]);
+    }
+}
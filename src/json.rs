@@ -0,0 +1,139 @@
+//! `also` variants serializing the value to JSON, for inspecting
+//! pipeline stages in a machine-readable form.
+
+use crate::ApplyAlso;
+use serde::Serialize;
+use std::io::Write;
+
+pub trait AlsoJson<T> {
+    /// Writes this value as a line of JSON to stderr. If the value
+    /// can not be serialized, the error is written instead.
+    /// Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoJson;
+    /// // prints "[1,2,3]"
+    /// let x = vec![1, 2, 3].also_json();
+    /// assert_eq!(x, vec![1, 2, 3]);
+    /// ```
+    fn also_json(self) -> T where T: Serialize;
+
+    /// Writes this value as pretty-printed JSON to stderr, like
+    /// [`also_json`](AlsoJson::also_json). Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoJson;
+    /// let x = ("alice", 42).also_json_pretty();
+    /// assert_eq!(x, ("alice", 42));
+    /// ```
+    fn also_json_pretty(self) -> T where T: Serialize;
+
+    /// Writes this value as a line of JSON to the given writer.
+    /// Returns the original value, or the error if the value could
+    /// not be serialized or written.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoJson;
+    /// let mut out = Vec::new();
+    /// let x = Some(5).also_json_to(&mut out).unwrap();
+    /// assert_eq!(x, Some(5));
+    /// assert_eq!(out, b"5\n");
+    /// ```
+    fn also_json_to(self, writer: impl Write) -> serde_json::Result<T> where T: Serialize;
+
+    /// Appends this value as a line of JSON to the given string.
+    /// Returns the original value, or the error if the value could
+    /// not be serialized.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoJson;
+    /// let mut sink = String::new();
+    /// let x = "a".also_json_into(&mut sink).unwrap();
+    /// let y = [true].also_json_into(&mut sink).unwrap();
+    /// assert_eq!(sink, "\"a\"\n[true]\n");
+    /// ```
+    fn also_json_into(self, sink: &mut String) -> serde_json::Result<T> where T: Serialize;
+}
+
+impl<T: ApplyAlso> AlsoJson<T> for T {
+    #[inline(always)]
+    fn also_json(self) -> T where T: Serialize {
+        match serde_json::to_string(&self) {
+            Ok(json) => eprintln!("{}", json),
+            Err(error) => eprintln!("<{}>", error),
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_json_pretty(self) -> T where T: Serialize {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => eprintln!("{}", json),
+            Err(error) => eprintln!("<{}>", error),
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_json_to(self, mut writer: impl Write) -> serde_json::Result<T> where T: Serialize {
+        serde_json::to_writer(&mut writer, &self)?;
+        writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+        Ok(self)
+    }
+
+    #[inline(always)]
+    fn also_json_into(self, sink: &mut String) -> serde_json::Result<T> where T: Serialize {
+        sink.push_str(&serde_json::to_string(&self)?);
+        sink.push('\n');
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Also;
+    use std::io;
+    use std::collections::BTreeMap;
+
+    /// Fails on every write, to test error propagation.
+    struct Closed;
+
+    impl Write for Closed {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn also_json() {
+        let map = BTreeMap::new().also_mut(|it| {
+            it.insert("b", 2);
+            it.insert("a", 1);
+        });
+        assert_eq!(map.clone().also_json().also_json_pretty(), map);
+    }
+
+    #[test]
+    fn also_json_to() {
+        let mut out = Vec::new();
+        let x = vec!["a", "b"].also_json_to(&mut out).unwrap();
+        assert_eq!(x, vec!["a", "b"]);
+        assert_eq!(out, b"[\"a\",\"b\"]\n");
+
+        let error = 1.also_json_to(Closed).unwrap_err();
+        assert!(error.is_io());
+
+        let mut sink = String::new();
+        (1, "x").also_json_into(&mut sink).unwrap();
+        assert_eq!(sink, "[1,\"x\"]\n");
+    }
+}
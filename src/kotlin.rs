@@ -0,0 +1,184 @@
+//! Kotlin's scope functions, with their Kotlin names and semantics.
+//!
+//! Unlike the crate's own [`Apply::apply`](crate::Apply::apply),
+//! [`KotlinScope::apply`] mutates the value and returns it, as in
+//! Kotlin. Import this module instead of the root traits, to avoid
+//! ambiguous method calls:
+//!
+//! ```
+//! use apply_also::kotlin::*;
+//!
+//! let list = Vec::new().apply(|it| it.push(1)).also(|it| println!("{:?}", it));
+//! let len = list.let_(|it| it.len());
+//! assert_eq!(len, 1);
+//! ```
+//!
+//! | Kotlin         | Receives | Returns           |
+//! |----------------|----------|-------------------|
+//! | `let`          | `T`      | the result        |
+//! | `run`          | `&mut T` | the result        |
+//! | `with`         | `&mut T` | the result        |
+//! | `apply`        | `&mut T` | the value         |
+//! | `also`         | `&T`     | the value         |
+//! | `takeIf`       | `&T`     | `Some` if true    |
+//! | `takeUnless`   | `&T`     | `Some` if false   |
+//!
+//! The trailing underscores of [`KotlinScope::let_`] and [`with_`]
+//! avoid the Rust keyword and the common method name.
+
+use crate::ApplyAlso;
+
+pub trait KotlinScope<T> {
+    /// Calls the given function with this value and returns its
+    /// result, like Kotlin's `let`. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::kotlin::KotlinScope;
+    /// let x = "42".let_(str::parse::<i32>);
+    /// assert_eq!(x, Ok(42));
+    /// ```
+    fn let_<R>(self, function: impl FnOnce(T) -> R) -> R;
+
+    /// Calls the given function with a mutable reference of this value
+    /// and returns its result, like Kotlin's `run`. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::kotlin::KotlinScope;
+    /// let last = vec![1, 2, 3].run(|it| {
+    ///     it.reverse();
+    ///     it[2]
+    /// });
+    /// assert_eq!(last, 1);
+    /// ```
+    fn run<R>(self, function: impl FnOnce(&mut T) -> R) -> R;
+
+    /// Calls the given function with a mutable reference of this value
+    /// and returns the value, like Kotlin's `apply`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::kotlin::KotlinScope;
+    /// let name = String::from("apply").apply(|it| it.push_str("_also"));
+    /// assert_eq!(name, "apply_also");
+    /// ```
+    fn apply(self, function: impl FnOnce(&mut T)) -> T;
+
+    /// Calls the given function with a reference of this value and
+    /// returns the value, like Kotlin's `also`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::kotlin::KotlinScope;
+    /// let mut seen = 0;
+    /// let x = 3.also(|it| seen = *it);
+    /// assert_eq!((x, seen), (3, 3));
+    /// ```
+    fn also(self, function: impl FnOnce(&T)) -> T;
+
+    /// Returns `Some` of this value if it satisfies the given
+    /// predicate, or `None` otherwise, like Kotlin's `takeIf`.
+    ///
+    /// Note that `Option` has an inherent `take_if` method, which
+    /// takes precedence over this one when called on an `Option`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::kotlin::KotlinScope;
+    /// assert_eq!(5.take_if(|it| *it > 3), Some(5));
+    /// ```
+    fn take_if(self, predicate: impl FnOnce(&T) -> bool) -> Option<T>;
+
+    /// Returns `Some` of this value if it does not satisfy the given
+    /// predicate, or `None` otherwise, like Kotlin's `takeUnless`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::kotlin::KotlinScope;
+    /// assert_eq!("".take_unless(|it| it.is_empty()), None);
+    /// ```
+    fn take_unless(self, predicate: impl FnOnce(&T) -> bool) -> Option<T>;
+}
+
+impl<T: ApplyAlso> KotlinScope<T> for T {
+    #[inline(always)]
+    fn let_<R>(self, function: impl FnOnce(T) -> R) -> R {
+        function(self)
+    }
+
+    #[inline(always)]
+    fn run<R>(mut self, function: impl FnOnce(&mut T) -> R) -> R {
+        function(&mut self)
+    }
+
+    #[inline(always)]
+    fn apply(mut self, function: impl FnOnce(&mut T)) -> T {
+        function(&mut self);
+        self
+    }
+
+    #[inline(always)]
+    fn also(self, function: impl FnOnce(&T)) -> T {
+        function(&self);
+        self
+    }
+
+    #[inline(always)]
+    fn take_if(self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
+        if predicate(&self) {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn take_unless(self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
+        if predicate(&self) {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+/// Calls the given function with a mutable reference of the given
+/// receiver and returns its result, like Kotlin's `with`.
+///
+/// Example:
+/// ```
+/// # use apply_also::kotlin::with_;
+/// let sum = with_(vec![1, 2], |it| {
+///     it.push(3);
+///     it.iter().sum::<i32>()
+/// });
+/// assert_eq!(sum, 6);
+/// ```
+#[inline(always)]
+pub fn with_<T, R>(mut receiver: T, function: impl FnOnce(&mut T) -> R) -> R {
+    function(&mut receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_functions() {
+        let mut log = Vec::new();
+        let x = 1
+            .apply(|it| *it += 1)
+            .also(|it| log.push(*it))
+            .let_(|it| it * 10)
+            .run(|it| {
+                *it += 1;
+                *it
+            });
+        assert_eq!(x, 21);
+        assert_eq!(log, vec![2]);
+        assert_eq!(with_(x, |it| *it + 1), 22);
+        assert_eq!(x.take_if(|it| *it > 20), Some(21));
+        assert_eq!(x.take_unless(|it| *it > 20), None);
+    }
+}
@@ -1,19 +1,21 @@
 //! Provides the higher order functions `apply` and `also` and variants
-//! `apply_ref`, `also_mut`, for simple object manipulation. 
+//! `apply_ref`, `apply_mut`, `also_mut`, for simple object manipulation. 
 //! They can be used to write cleaner initialization of variables 
 //! (see example below). `also` can also be used similarly to unix' 
 //! `tee` command.
 //! Inspired by Kotlin's `apply` and `also` functions.
 //!
 //! ```
-//! # use std::collections::HashMap;
 //! use apply_also::{ Apply, Also };
 //!
 //! // also:
+//! # #[cfg(feature = "std")] {
+//! # use std::collections::HashMap;
 //! let map = HashMap::new().also_mut(|it| {
 //!     it.insert("hello", "world");
 //! });
 //! assert_eq!(map.get("hello"), Some(&"world"));
+//! # }
 //!
 //! // apply:
 //! let x = 256.apply(|it| it * 2);
@@ -26,8 +28,296 @@
 //!     })
 //! }
 //! ```
+//!
+//! The crate is `no_std` compatible. Disabling the default `std`
+//! feature leaves the core traits, which only depend on `core`.
+//! The `alloc` feature adds the implementations for `Vec`, `Box`, `Rc` and `Arc`,
+//! as well as the [`pipeline`] module.
+//!
+//! The [`guard`] module provides scope guards, which pair well with
+//! `also` for setup and teardown, the [`owned`] module owning
+//! projections to parts of a value, and the [`piped`] module operator
+//! pipelines with `>>`. The [`applier`] module packages reusable
+//! transformations, and the [`fns`] module free function mirrors of
+//! `apply` and `also`, for passing to iterator adapters. The [`retry`]
+//! module provides retry policies with fixed, exponential and jittered delays, the
+//! [`cache`] module memoization with a bounded cache, and the
+//! [`testing`] module utilities for observing values in tests.
+//!
+//! # Name conflicts
+//!
+//! By default, the traits are implemented for every type, but their
+//! methods are only available where the trait is imported. If a method conflicts
+//! with a trait of your own, import only the traits you need, or call
+//! the method fully qualified, e.g. `Apply::apply(value, f)`.
+//!
+//! The [`prelude`] imports all traits at once. With the `compat`
+//! feature, `ApplyCompat` provides the aliases `k_apply`, `k_also`,
+//! `k_tee` and `k_take_if`, which do not clash with other crates.
+//!
+//! The extension traits, like [`Apply`] and [`Also`], are implemented
+//! for the types implementing the [`ApplyAlso`] marker. With the default
+//! `blanket` feature, that is every type. Without it, only the types
+//! opted in with [`impl_apply!`] have the methods:
+//!
+//! ```
+//! // with `default-features = false, features = ["std"]`
+//! use apply_also::{ impl_apply, Also };
+//!
+//! #[derive(Default)]
+//! struct Config { retries: u32 }
+//!
+//! impl_apply!(Config);
+//!
+//! let config = Config::default().also_mut(|it| it.retries = 3);
+//! assert_eq!(config.retries, 3);
+//! ```
+//!
+//! If another crate in the dependency graph enables `blanket`, every
+//! type has the methods, and `impl_apply!` expands to nothing, so crates
+//! using it keep compiling. The core traits are sealed: they can not be
+//! implemented outside of this crate.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::borrow::{ Borrow, BorrowMut };
+use core::convert::TryInto;
+use core::ops::Deref;
+
+/// Marker for the types the extension traits are implemented for.
+///
+/// With the `blanket` feature, it is implemented for every type.
+/// Otherwise, implement it with [`impl_apply!`].
+pub trait ApplyAlso {}
+
+#[cfg(any(feature = "blanket", test))]
+impl<T: ?Sized> ApplyAlso for T {}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl<T: crate::ApplyAlso + ?Sized> Sealed for T {}
+}
+
+mod assert;
+mod atomic;
+mod cell;
+mod chain;
+mod compose;
+mod dynamic;
+mod ensure;
+mod init;
+mod inner;
+mod iter;
+mod macros;
+mod option;
+mod parse;
+mod pin;
+mod repeat;
+mod result;
+mod scoped;
+mod tuple;
+mod update;
+
+pub mod applier;
+pub mod fns;
+pub mod guard;
+pub mod owned;
+pub mod piped;
+pub mod prelude;
+
+pub use applier::ApplyApplier;
+pub use assert::AlsoAssert;
+pub use atomic::AtomicApplyExt;
+pub use cell::{ CellApplyExt, OnceApplyExt, RefCellApplyExt };
+pub use chain::ApplyChain;
+pub use compose::ThenFn;
+pub use dynamic::{ ApplyDyn, AlsoDyn };
+pub use ensure::ApplyEnsuring;
+pub use guard::AlsoOnDrop;
+pub use init::{ apply_init, init, init_array, init_with };
+pub use inner::{ ApplyEach, ApplyInner };
+pub use iter::IteratorAlsoExt;
+pub use option::OptionApplyExt;
+pub use owned::ApplyOwned;
+pub use parse::ParseApplyExt;
+pub use pin::ApplyPinned;
+pub use repeat::ApplyRepeat;
+pub use result::{ ResultAlsoExt, ResultApplyExt };
+pub use scoped::ApplyScoped;
+pub use tuple::{ Apply2, Apply3, ApplyBoth, ApplyZip };
+pub use update::Update;
+
+#[cfg(feature = "std")]
+pub mod cache;
+
+#[cfg(feature = "std")]
+mod channel;
+
+#[cfg(feature = "std")]
+pub mod limit;
+
+#[cfg(feature = "std")]
+mod print;
+
+#[cfg(feature = "std")]
+pub mod retry;
+
+#[cfg(feature = "std")]
+mod sync;
+
+#[cfg(feature = "std")]
+mod tee;
+
+#[cfg(feature = "std")]
+pub mod testing;
+
+#[cfg(feature = "std")]
+mod time;
+
+#[cfg(feature = "std")]
+mod unwind;
+
+#[cfg(feature = "std")]
+pub use cache::ApplyCached;
+
+#[cfg(feature = "std")]
+pub use channel::{ AlsoSend, TeeSender };
+
+#[cfg(feature = "tokio")]
+pub use channel::AlsoSendAsync;
+
+#[cfg(feature = "std")]
+pub use limit::{ AlsoLimited, seed_sample };
+
+#[cfg(feature = "std")]
+pub use print::AlsoPrint;
+
+#[cfg(feature = "std")]
+pub use retry::ApplyRetryWith;
+
+#[cfg(feature = "std")]
+pub use sync::{ MutexApplyExt, RwLockApplyExt };
+
+#[cfg(feature = "std")]
+pub use tee::{ ReadTeeExt, WriteTeeExt, TeeReader, TeeWriter };
+
+#[cfg(feature = "std")]
+pub use testing::AlsoRecord;
+
+#[cfg(feature = "std")]
+pub use time::ApplyTimed;
+
+#[cfg(feature = "std")]
+pub use unwind::ApplyCatch;
+
+#[cfg(feature = "compat")]
+mod compat;
+
+#[cfg(feature = "compat")]
+pub use compat::ApplyCompat;
 
-pub trait Apply<T, R> {
+#[cfg(feature = "hooks")]
+pub mod hooks;
+
+#[cfg(feature = "hooks")]
+pub use hooks::AlsoHooked;
+
+#[cfg(feature = "kotlin-names")]
+pub mod kotlin;
+
+#[cfg(feature = "log")]
+mod logging;
+
+#[cfg(feature = "log")]
+pub use logging::AlsoLog;
+
+#[cfg(feature = "metrics")]
+mod metric;
+
+#[cfg(feature = "metrics")]
+pub use metric::AlsoMetrics;
+
+#[cfg(feature = "rayon")]
+mod par;
+
+#[cfg(feature = "rayon")]
+pub use par::ParApplyExt;
+
+#[cfg(feature = "serde")]
+mod json;
+
+#[cfg(feature = "serde")]
+pub use json::AlsoJson;
+
+#[cfg(feature = "tap-compat")]
+pub mod tapped;
+
+#[cfg(feature = "tracing")]
+mod trace;
+
+#[cfg(feature = "tracing")]
+pub use trace::AlsoTrace;
+
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing as __tracing;
+
+#[cfg(feature = "alloc")]
+mod cow;
+
+#[cfg(feature = "alloc")]
+mod map;
+
+#[cfg(feature = "alloc")]
+pub mod pipeline;
+
+#[cfg(feature = "alloc")]
+mod rc;
+
+#[cfg(feature = "alloc")]
+pub use cow::CowApplyExt;
+
+#[cfg(feature = "alloc")]
+pub use map::MapAlsoExt;
+
+#[cfg(feature = "alloc")]
+pub use pipeline::ApplyTraced;
+
+#[cfg(feature = "alloc")]
+pub use rc::AlsoMakeMut;
+
+#[cfg(feature = "derive")]
+pub use apply_also_derive::{ extension, With };
+
+#[cfg(feature = "async")]
+mod future;
+
+#[cfg(all(test, feature = "async"))]
+mod test_util;
+
+#[cfg(feature = "async")]
+pub use future::{ ApplyAsync, AlsoAsync };
+
+#[cfg(feature = "futures")]
+mod stream;
+
+#[cfg(feature = "futures")]
+pub use future::FutureApplyExt;
+
+#[cfg(feature = "futures")]
+pub use stream::{ StreamAlsoExt, AlsoEach, AlsoEachMut };
+
+#[cfg(feature = "futures-io")]
+mod async_tee;
+
+#[cfg(feature = "futures-io")]
+pub use async_tee::{ AsyncReadTeeExt, AsyncWriteTeeExt, AsyncTeeReader, AsyncTeeWriter };
+
+pub trait Apply<T, R>: sealed::Sealed {
     /// Applies the given function to this value and
     /// returns the result. Consumes the value.
     ///
@@ -56,9 +346,197 @@ pub trait Apply<T, R> {
     /// assert_eq!(x, 3);
     /// ```
     fn apply_ref(self, function: impl FnOnce(&T) -> R) -> R;
+
+    /// Applies the given function to a mutable reference of this
+    /// value and returns the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, Also };
+    /// let x = vec![1, 2, 3].apply_mut(Vec::pop);
+    /// assert_eq!(x, Some(3));
+    /// ```
+    fn apply_mut(self, function: impl FnOnce(&mut T) -> R) -> R;
+
+    /// Applies the given function to a reference of this value,
+    /// converted with `AsRef`, and returns the result.
+    /// Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Apply;
+    /// use std::path::{ Path, PathBuf };
+    ///
+    /// let absolute = PathBuf::from("/etc/hosts").apply_as_ref(Path::is_absolute);
+    /// assert!(absolute);
+    /// ```
+    fn apply_as_ref<U: ?Sized>(self, function: impl FnOnce(&U) -> R) -> R where T: AsRef<U>;
+
+    /// Applies the given function to a reference of the target this
+    /// value dereferences to, and returns the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Apply;
+    /// struct Config { retries: u32 }
+    ///
+    /// let x = Box::new(Config { retries: 3 }).apply_deref(|it: &Config| it.retries);
+    /// assert_eq!(x, 3);
+    /// ```
+    fn apply_deref(self, function: impl FnOnce(&T::Target) -> R) -> R where T: Deref;
+
+    /// Converts this value with `Into`, applies the given function to
+    /// it and returns the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Apply;
+    /// use std::path::PathBuf;
+    ///
+    /// let x = "/tmp".to_string().apply_into(|it: PathBuf| it.join("file"));
+    /// assert_eq!(x, PathBuf::from("/tmp/file"));
+    /// ```
+    fn apply_into<U>(self, function: impl FnOnce(U) -> R) -> R where T: Into<U>;
+
+    /// Converts this value with `TryInto`, applies the given function
+    /// to it and returns the result, or the conversion error.
+    /// Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Apply;
+    /// let x = 300u32.apply_try_into(|it: u16| it * 2);
+    /// assert_eq!(x, Ok(600));
+    ///
+    /// let y = 300u32.apply_try_into(|it: u8| it * 2);
+    /// assert!(y.is_err());
+    /// ```
+    fn apply_try_into<U>(self, function: impl FnOnce(U) -> R) -> Result<R, <T as TryInto<U>>::Error> where T: TryInto<U>;
+
+    /// Applies the given function to this value and the given context,
+    /// e.g. a configuration or a connection, and returns the result.
+    /// Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Apply;
+    /// struct Config { scale: i32 }
+    ///
+    /// let config = Config { scale: 2 };
+    /// let x = 256.apply_with(&config, |it, config| it * config.scale);
+    /// assert_eq!(x, 512);
+    /// ```
+    fn apply_with<C>(self, context: C, function: impl FnOnce(T, C) -> R) -> R;
+}
+
+pub trait TryApply<T, R, E>: sealed::Sealed {
+    /// Applies the given fallible function to this value and
+    /// returns the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::TryApply;
+    /// # fn main() -> Result<(), std::num::ParseIntError> {
+    /// let x = "256".try_apply(str::parse::<i32>)?;
+    /// assert_eq!(x, 256);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_apply(self, function: impl FnOnce(T) -> Result<R, E>) -> Result<R, E>;
+
+    /// Applies the given fallible function to a reference of this
+    /// value and returns the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::TryApply;
+    /// let x = String::from("abc").try_apply_ref(|it| it.parse::<i32>());
+    /// assert!(x.is_err());
+    /// ```
+    fn try_apply_ref(self, function: impl FnOnce(&T) -> Result<R, E>) -> Result<R, E>;
+
+    /// Applies the given fallible function to a reference of this
+    /// value until it succeeds, calling it at most `attempts` times,
+    /// but at least once. Returns the first success, or the last
+    /// error. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::TryApply;
+    /// let mut calls = 0;
+    /// let x = "localhost".apply_retry(3, |host| {
+    ///     calls += 1;
+    ///     if calls < 3 { Err("connection refused") } else { Ok(format!("connected to {}", host)) }
+    /// });
+    /// assert_eq!(x.as_deref(), Ok("connected to localhost"));
+    /// ```
+    fn apply_retry(self, attempts: usize, function: impl FnMut(&T) -> Result<R, E>) -> Result<R, E>;
+}
+
+pub trait ApplyRef<T: ?Sized, R>: sealed::Sealed {
+    /// Applies the given function to a reference of this value 
+    /// and returns the result. Does not consume the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRef;
+    /// let v = vec![1, 2, 3];
+    /// let x = v.apply_by_ref(|it| it.len());
+    /// assert_eq!(x, 3);
+    /// assert_eq!(v, vec![1, 2, 3]);
+    /// ```
+    fn apply_by_ref(&self, function: impl FnOnce(&T) -> R) -> R;
+
+    /// Applies the given function to a mutable reference of this 
+    /// value and returns the result. Does not consume the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRef;
+    /// let mut v = vec![1, 2, 3];
+    /// let x = v.apply_by_mut(Vec::pop);
+    /// assert_eq!(x, Some(3));
+    /// assert_eq!(v, vec![1, 2]);
+    /// ```
+    fn apply_by_mut(&mut self, function: impl FnOnce(&mut T) -> R) -> R;
 }
 
-pub trait Also<T> {
+pub trait ApplyIf<T>: sealed::Sealed {
+    /// Applies the given function to this value if the condition
+    /// holds and returns the result. Otherwise, returns the value
+    /// unchanged. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyIf;
+    /// let verbose = true;
+    /// let args = vec!["run"].apply_if(verbose, |mut it| {
+    ///     it.push("--verbose");
+    ///     it
+    /// });
+    /// assert_eq!(args, vec!["run", "--verbose"]);
+    /// ```
+    fn apply_if(self, condition: bool, function: impl FnOnce(T) -> T) -> T;
+
+    /// Applies the first function to this value if the condition
+    /// holds, and the second function otherwise. Returns the result.
+    /// Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyIf;
+    /// let x = 256.apply_if_else(false, |it| it * 2, |it| it / 2);
+    /// assert_eq!(x, 128);
+    /// ```
+    fn apply_if_else<R>(
+        self,
+        condition: bool,
+        function: impl FnOnce(T) -> R,
+        otherwise: impl FnOnce(T) -> R,
+    ) -> R;
+}
+
+pub trait Also<T>: sealed::Sealed {
     /// Applies the given function to a reference of this value.
     /// Returns the original value.
     ///
@@ -70,7 +548,7 @@ pub trait Also<T> {
     /// });
     /// assert_eq!(x, 3);
     /// ```
-    fn also(self, function: impl FnOnce(&T) -> ()) -> T;
+    fn also(self, function: impl FnOnce(&T)) -> T;
 
     /// Applies the given function to a mutable reference of this value.
     /// Returns the mutated value.
@@ -84,29 +562,433 @@ pub trait Also<T> {
     /// });
     /// assert_eq!(x, vec!["hello", "world"]);
     /// ```
-    fn also_mut(self, function: impl FnOnce(&mut T) -> ()) -> T;
+    fn also_mut(self, function: impl FnOnce(&mut T)) -> T;
+
+    /// Applies the given function to a reference of this value if
+    /// the condition holds. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, Also };
+    /// let debug = false;
+    /// let x = 3.also_if(debug, |it| {
+    ///     println!("x = {}", it);
+    /// });
+    /// assert_eq!(x, 3);
+    /// ```
+    fn also_if(self, condition: bool, function: impl FnOnce(&T)) -> T;
+
+    /// Applies the given function to a mutable reference of this value
+    /// if the condition holds. Returns the (possibly mutated) value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, Also };
+    /// let x = Vec::new()
+    ///     .also_mut(|it| it.push("hello"))
+    ///     .also_mut_if(false, |it| it.push("cruel"))
+    ///     .also_mut_if(true, |it| it.push("world"));
+    /// assert_eq!(x, vec!["hello", "world"]);
+    /// ```
+    fn also_mut_if(self, condition: bool, function: impl FnOnce(&mut T)) -> T;
+
+    /// Applies the given function to a reference of this value in
+    /// debug builds. In release builds, the function is never called,
+    /// so it is compiled out entirely. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, Also };
+    /// let x = 256.apply(|it| it * 2).also_debug_only(|it| {
+    ///     assert!(it % 2 == 0, "expensive invariant check");
+    /// });
+    /// assert_eq!(x, 512);
+    /// ```
+    fn also_debug_only(self, function: impl FnOnce(&T)) -> T;
+
+    /// Applies the given function to a borrowed view of this value,
+    /// e.g. the `str` of a `String`. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Also;
+    /// let x = String::from("hello").also_borrow_as(|it: &str| {
+    ///     assert!(it.starts_with("he"));
+    /// });
+    /// assert_eq!(x, "hello");
+    ///
+    /// let bytes = vec![1u8, 2].also_borrow_as::<[u8]>(|it| println!("{:?}", it));
+    /// assert_eq!(bytes, [1, 2]);
+    /// ```
+    fn also_borrow_as<B: ?Sized>(self, function: impl FnOnce(&B)) -> T where T: Borrow<B>;
+
+    /// Applies the given function to a mutably borrowed view of this
+    /// value, e.g. the `[T]` of a `Vec<T>`. Returns the mutated value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Also;
+    /// let x = vec![3, 1, 2].also_borrow_mut_as(<[i32]>::sort);
+    /// assert_eq!(x, vec![1, 2, 3]);
+    /// ```
+    fn also_borrow_mut_as<B: ?Sized>(self, function: impl FnOnce(&mut B)) -> T where T: BorrowMut<B>;
+
+    /// Applies the given function to a reference of the target this
+    /// value dereferences to. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Also;
+    /// use std::sync::Arc;
+    ///
+    /// let state = Arc::new(vec![1, 2]).also_deref(|it: &Vec<i32>| {
+    ///     println!("{} entries", it.len());
+    /// });
+    /// assert_eq!(*state, vec![1, 2]);
+    /// ```
+    fn also_deref(self, function: impl FnOnce(&T::Target)) -> T where T: Deref;
+
+    /// Applies the given function to a reference of this value and a
+    /// reference of the given context. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Also;
+    /// let rows = vec![1, 2].also_with("import", |it, stage| println!("{}: {} rows", stage, it.len()));
+    /// assert_eq!(rows, vec![1, 2]);
+    /// ```
+    fn also_with<C: ?Sized>(self, context: &C, function: impl FnOnce(&T, &C)) -> T;
+}
+
+pub trait TryAlso<T, E>: sealed::Sealed {
+    /// Applies the given fallible function to a reference of this value.
+    /// Returns the original value, or the error returned by the function.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::TryAlso;
+    /// let x: Result<_, String> = 3.try_also(|it| {
+    ///     if *it > 0 { Ok(()) } else { Err("not positive".into()) }
+    /// });
+    /// assert_eq!(x, Ok(3));
+    /// ```
+    fn try_also(self, function: impl FnOnce(&T) -> Result<(), E>) -> Result<T, E>;
+
+    /// Applies the given fallible function to a mutable reference of this
+    /// value. Returns the mutated value, or the error returned by the function.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::TryAlso;
+    /// # fn main() -> Result<(), std::num::ParseIntError> {
+    /// let x = Vec::new().try_also_mut(|it| {
+    ///     it.push("1".parse::<i32>()?);
+    ///     it.push("2".parse::<i32>()?);
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(x, vec![1, 2]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_also_mut(self, function: impl FnOnce(&mut T) -> Result<(), E>) -> Result<T, E>;
+}
+
+pub trait AlsoRef<T: ?Sized>: sealed::Sealed {
+    /// Applies the given function to a reference of this value.
+    /// Returns the reference, so calls can be chained without
+    /// moving the value out of its owner.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoRef;
+    /// let v = vec![1, 2, 3];
+    /// let x = v.also_by_ref(|it| {
+    ///     println!("{:?}", it);
+    /// });
+    /// assert_eq!(x, &vec![1, 2, 3]);
+    /// ```
+    fn also_by_ref(&self, function: impl FnOnce(&T)) -> &T;
+
+    /// Applies the given function to a mutable reference of this value.
+    /// Returns the mutable reference, so calls can be chained without
+    /// moving the value out of its owner.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoRef;
+    /// struct Config { retries: u32, verbose: bool }
+    ///
+    /// let mut config = Config { retries: 0, verbose: false };
+    /// config
+    ///     .also_by_mut(|it| it.retries = 3)
+    ///     .also_by_mut(|it| it.verbose = true);
+    /// assert_eq!(config.retries, 3);
+    /// assert!(config.verbose);
+    /// ```
+    fn also_by_mut(&mut self, function: impl FnOnce(&mut T)) -> &mut T;
+}
+
+pub trait Convert<T>: sealed::Sealed {
+    /// Converts this value with `Into`. Unlike `into`, the target type
+    /// can be given with a turbofish, which keeps method chains readable.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, Convert };
+    /// let x = 5u8.convert::<u32>().apply(|it| it << 10);
+    /// assert_eq!(x, 5120);
+    /// ```
+    fn convert<U>(self) -> U where T: Into<U>;
+}
+
+pub trait TakeIf<T>: sealed::Sealed {
+    /// Returns `Some` of this value if it satisfies the given
+    /// predicate, or `None` otherwise. Consumes the value.
+    ///
+    /// Note that `Option` has an inherent `take_if` method, which
+    /// takes precedence over this one when called on an `Option`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::TakeIf;
+    /// let x = 5.take_if(|it| *it > 3);
+    /// assert_eq!(x, Some(5));
+    /// let y = 2.take_if(|it| *it > 3);
+    /// assert_eq!(y, None);
+    /// ```
+    fn take_if(self, predicate: impl FnOnce(&T) -> bool) -> Option<T>;
+
+    /// Returns `Some` of this value if it does not satisfy the
+    /// given predicate, or `None` otherwise. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::TakeIf;
+    /// let x = String::from("hello").take_unless(String::is_empty);
+    /// assert_eq!(x.as_deref(), Some("hello"));
+    /// ```
+    fn take_unless(self, predicate: impl FnOnce(&T) -> bool) -> Option<T>;
 }
 
-impl<T, R> Apply<T, R> for T {
+impl<T: ApplyAlso, R> Apply<T, R> for T {
+    #[inline(always)]
     fn apply(self, function: impl FnOnce(T) -> R) -> R {
         function(self)
     }
 
+    #[inline(always)]
     fn apply_ref(self, function: impl FnOnce(&T) -> R) -> R {
         function(&self)
     }
+
+    #[inline(always)]
+    fn apply_mut(mut self, function: impl FnOnce(&mut T) -> R) -> R {
+        function(&mut self)
+    }
+
+    #[inline(always)]
+    fn apply_as_ref<U: ?Sized>(self, function: impl FnOnce(&U) -> R) -> R where T: AsRef<U> {
+        function(self.as_ref())
+    }
+
+    #[inline(always)]
+    fn apply_deref(self, function: impl FnOnce(&T::Target) -> R) -> R where T: Deref {
+        function(&self)
+    }
+
+    #[inline(always)]
+    fn apply_into<U>(self, function: impl FnOnce(U) -> R) -> R where T: Into<U> {
+        function(self.into())
+    }
+
+    #[inline(always)]
+    fn apply_try_into<U>(self, function: impl FnOnce(U) -> R) -> Result<R, <T as TryInto<U>>::Error> where T: TryInto<U> {
+        self.try_into().map(function)
+    }
+
+    #[inline(always)]
+    fn apply_with<C>(self, context: C, function: impl FnOnce(T, C) -> R) -> R {
+        function(self, context)
+    }
 }
 
-impl<T> Also<T> for T {
-    fn also(self, function: impl FnOnce(&T) -> ()) -> T {
+impl<T: ApplyAlso, R, E> TryApply<T, R, E> for T {
+    #[inline(always)]
+    fn try_apply(self, function: impl FnOnce(T) -> Result<R, E>) -> Result<R, E> {
+        function(self)
+    }
+
+    #[inline(always)]
+    fn try_apply_ref(self, function: impl FnOnce(&T) -> Result<R, E>) -> Result<R, E> {
+        function(&self)
+    }
+
+    #[inline(always)]
+    fn apply_retry(self, attempts: usize, mut function: impl FnMut(&T) -> Result<R, E>) -> Result<R, E> {
+        let mut result = function(&self);
+        for _ in 1..attempts {
+            if result.is_ok() {
+                break;
+            }
+            result = function(&self);
+        }
+        result
+    }
+}
+
+impl<T: ApplyAlso + ?Sized, R> ApplyRef<T, R> for T {
+    #[inline(always)]
+    fn apply_by_ref(&self, function: impl FnOnce(&T) -> R) -> R {
+        function(self)
+    }
+
+    #[inline(always)]
+    fn apply_by_mut(&mut self, function: impl FnOnce(&mut T) -> R) -> R {
+        function(self)
+    }
+}
+
+impl<T: ApplyAlso> ApplyIf<T> for T {
+    #[inline(always)]
+    fn apply_if(self, condition: bool, function: impl FnOnce(T) -> T) -> T {
+        if condition {
+            function(self)
+        } else {
+            self
+        }
+    }
+
+    #[inline(always)]
+    fn apply_if_else<R>(
+        self,
+        condition: bool,
+        function: impl FnOnce(T) -> R,
+        otherwise: impl FnOnce(T) -> R,
+    ) -> R {
+        if condition {
+            function(self)
+        } else {
+            otherwise(self)
+        }
+    }
+}
+
+impl<T: ApplyAlso> Also<T> for T {
+    #[inline(always)]
+    fn also(self, function: impl FnOnce(&T)) -> T {
         function(&self);
         self
     }
 
-    fn also_mut(mut self, function: impl FnOnce(&mut T) -> ()) -> T {
+    #[inline(always)]
+    fn also_mut(mut self, function: impl FnOnce(&mut T)) -> T {
         function(&mut self);
         self
     }
+
+    #[inline(always)]
+    fn also_if(self, condition: bool, function: impl FnOnce(&T)) -> T {
+        if condition {
+            function(&self);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_mut_if(mut self, condition: bool, function: impl FnOnce(&mut T)) -> T {
+        if condition {
+            function(&mut self);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_debug_only(self, function: impl FnOnce(&T)) -> T {
+        #[cfg(debug_assertions)]
+        function(&self);
+        #[cfg(not(debug_assertions))]
+        drop(function);
+        self
+    }
+
+    #[inline(always)]
+    fn also_borrow_as<B: ?Sized>(self, function: impl FnOnce(&B)) -> T where T: Borrow<B> {
+        function(self.borrow());
+        self
+    }
+
+    #[inline(always)]
+    fn also_borrow_mut_as<B: ?Sized>(mut self, function: impl FnOnce(&mut B)) -> T where T: BorrowMut<B> {
+        function(self.borrow_mut());
+        self
+    }
+
+    #[inline(always)]
+    fn also_deref(self, function: impl FnOnce(&T::Target)) -> T where T: Deref {
+        function(&self);
+        self
+    }
+
+    #[inline(always)]
+    fn also_with<C: ?Sized>(self, context: &C, function: impl FnOnce(&T, &C)) -> T {
+        function(&self, context);
+        self
+    }
+}
+
+impl<T: ApplyAlso + ?Sized> AlsoRef<T> for T {
+    #[inline(always)]
+    fn also_by_ref(&self, function: impl FnOnce(&T)) -> &T {
+        function(self);
+        self
+    }
+
+    #[inline(always)]
+    fn also_by_mut(&mut self, function: impl FnOnce(&mut T)) -> &mut T {
+        function(self);
+        self
+    }
+}
+
+impl<T: ApplyAlso> Convert<T> for T {
+    #[inline(always)]
+    fn convert<U>(self) -> U where T: Into<U> {
+        self.into()
+    }
+}
+
+impl<T: ApplyAlso> TakeIf<T> for T {
+    #[inline(always)]
+    fn take_if(self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
+        if predicate(&self) {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn take_unless(self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
+        if predicate(&self) {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+impl<T: ApplyAlso, E> TryAlso<T, E> for T {
+    #[inline(always)]
+    fn try_also(self, function: impl FnOnce(&T) -> Result<(), E>) -> Result<T, E> {
+        function(&self)?;
+        Ok(self)
+    }
+
+    #[inline(always)]
+    fn try_also_mut(mut self, function: impl FnOnce(&mut T) -> Result<(), E>) -> Result<T, E> {
+        function(&mut self)?;
+        Ok(self)
+    }
 }
 
 
@@ -135,6 +1017,92 @@ mod tests {
         assert_eq!(y, 3);
     }
 
+    #[test]
+    fn apply_mut() {
+        let y = vec![1, 2, 3].apply_mut(|it| {
+            it.push(4);
+            it.len()
+        });
+        assert_eq!(y, 4);
+    }
+
+    #[test]
+    fn apply_as_ref() {
+        let x = String::from("abc").apply_as_ref(|it: &[u8]| it[0]);
+        assert_eq!(x, b'a');
+        assert_eq!(String::from("abc").apply_as_ref::<str>(|it| it.len()), 3);
+    }
+
+    #[test]
+    fn apply_deref() {
+        let x = Box::new(5).apply_deref(|it| it + 1);
+        assert_eq!(x, 6);
+
+        let mut seen = String::new();
+        let y = String::from("abc").also_deref(|it: &str| seen.push_str(it));
+        assert_eq!((y.as_str(), seen.as_str()), ("abc", "abc"));
+    }
+
+    #[test]
+    fn apply_into() {
+        let x = 'a'.apply_into(|it: u32| it + 1);
+        assert_eq!(x, 98);
+        assert_eq!("abc".convert::<String>(), "abc");
+    }
+
+    #[test]
+    fn apply_try_into() {
+        assert_eq!((-1i64).apply_try_into(|it: i8| it - 1), Ok(-2));
+        assert!((-1i64).apply_try_into(|it: u64| it).is_err());
+    }
+
+    #[test]
+    fn apply_with() {
+        let suffix = String::from("!");
+        let x = "hi".apply_with(&suffix, |it, suffix| format!("{}{}", it, suffix));
+        assert_eq!(x, "hi!");
+        assert_eq!(2.apply_with(3, |it, by| it * by), 6);
+
+        let mut seen = Vec::new();
+        let y = 5.also_with("ctx", |it, ctx| seen.push(format!("{} {}", ctx, it)));
+        assert_eq!((y, seen), (5, vec!["ctx 5".to_string()]));
+    }
+
+    #[test]
+    fn try_apply() {
+        let x: Result<i32, _> = "12".try_apply(str::parse);
+        assert_eq!(x, Ok(12));
+
+        let y = String::from("x").try_apply_ref(|it| it.parse::<i32>());
+        assert!(y.is_err());
+    }
+
+    #[test]
+    fn apply_retry() {
+        let mut calls = 0;
+        let x: Result<(), _> = 1.apply_retry(3, |_| {
+            calls += 1;
+            Err(calls)
+        });
+        assert_eq!(x, Err(3));
+
+        let mut calls = 0;
+        assert_eq!(2.apply_retry(0, |it| { calls += 1; Err::<i32, _>(*it) }), Err(2));
+        assert_eq!(calls, 1);
+        assert_eq!(2.apply_retry(5, |it| Ok::<_, ()>(it * 2)), Ok(4));
+    }
+
+    #[test]
+    fn apply_if() {
+        assert_eq!(2.apply_if(true, |it| it + 1), 3);
+        assert_eq!(2.apply_if(false, |it| it + 1), 2);
+
+        let x = "5".apply_if_else(true, String::from, |it| it.repeat(2));
+        assert_eq!(x, "5");
+        let y = "5".apply_if_else(false, String::from, |it| it.repeat(2));
+        assert_eq!(y, "55");
+    }
+
     #[test]
     fn also() {
         let x = Vec::new().also_mut(|it| {
@@ -143,4 +1111,74 @@ mod tests {
         });
         assert_eq!(x, vec!["hello", "world"]);
     }
+
+    #[test]
+    fn also_if() {
+        let mut calls = 0;
+        let x = 1.also_if(false, |_| calls += 1).also_if(true, |_| calls += 1);
+        assert_eq!((x, calls), (1, 1));
+
+        let y = vec![1]
+            .also_mut_if(true, |it| it.push(2))
+            .also_mut_if(false, |it| it.push(3));
+        assert_eq!(y, vec![1, 2]);
+    }
+
+    #[test]
+    fn also_debug_only() {
+        let mut called = false;
+        let x = 1.also_debug_only(|_| called = true);
+        assert_eq!((x, called), (1, cfg!(debug_assertions)));
+    }
+
+    #[test]
+    fn also_borrow_as() {
+        let mut len = 0;
+        let x = String::from("abc").also_borrow_as(|it: &str| len = it.len());
+        assert_eq!((x.as_str(), len), ("abc", 3));
+
+        let y = vec![2, 1].also_borrow_mut_as(|it: &mut [i32]| it.reverse());
+        assert_eq!(y, vec![1, 2]);
+    }
+
+    #[test]
+    fn apply_by_ref() {
+        let mut v = vec![3, 1, 2];
+        assert_eq!(v.apply_by_ref(|it| it.len()), 3);
+        assert_eq!(v.apply_by_mut(Vec::pop), Some(2));
+        assert_eq!("hello".apply_by_ref(str::len), 5);
+    }
+
+    #[test]
+    fn also_by_ref() {
+        let mut v = vec![3, 1, 2];
+        v.also_by_mut(|it| it.push(0)).also_by_mut(|it| it.sort());
+        assert_eq!(v, vec![0, 1, 2, 3]);
+
+        let slice: &mut [i32] = &mut v;
+        slice.also_by_mut(|it| it.reverse());
+        assert_eq!(v.also_by_ref(|_| ()), &vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn try_also() {
+        let x: Result<_, ()> = Vec::new().try_also_mut(|it| {
+            it.push(1);
+            Ok(())
+        });
+        assert_eq!(x, Ok(vec![1]));
+
+        let y = 5.try_also(|_| Err("error"));
+        assert_eq!(y, Err("error"));
+    }
+
+    #[test]
+    fn take_if() {
+        assert_eq!(4.take_if(|it| it % 2 == 0), Some(4));
+        assert_eq!(3.take_if(|it| it % 2 == 0), None);
+        assert_eq!(4.take_unless(|it| it % 2 == 0), None);
+
+        let x = "  ".take_unless(|it| it.trim().is_empty()).map(str::len);
+        assert_eq!(x, None);
+    }
 }
@@ -0,0 +1,410 @@
+//! `also` variants running the function only for some of the calls,
+//! for keeping tees cheap and quiet on hot paths.
+//!
+//! The `also_once` and `also_throttle` methods identify their call site
+//! by its location in the source code, looked up in a global table on
+//! every call. On hot paths, prefer the [`also_once!`](crate::also_once)
+//! and [`also_throttle!`](crate::also_throttle) macros, which keep the
+//! state of each call site in a static of its own and only use atomics.
+//!
+//! ```
+//! use apply_also::{ also_once, also_throttle };
+//! use std::time::Duration;
+//!
+//! let mut logged = Vec::new();
+//! for i in 0..1000 {
+//!     let i = also_once!(i, |it| logged.push(*it));
+//!     also_throttle!(i, Duration::from_secs(60), |it| logged.push(*it));
+//! }
+//! assert_eq!(logged, vec![0, 0]);
+//! ```
+
+use crate::ApplyAlso;
+use core::cell::Cell;
+use core::panic::Location;
+use std::collections::{ BTreeMap, BTreeSet };
+use std::collections::hash_map::RandomState;
+use std::hash::{ BuildHasher, Hasher };
+use std::sync::atomic::{ AtomicBool, AtomicU64, Ordering };
+use std::sync::{ Mutex, OnceLock, PoisonError };
+use std::time::{ Duration, Instant };
+
+/// The call sites whose `also_once` function already ran.
+static CALLED: Mutex<BTreeSet<&'static Location<'static>>> = Mutex::new(BTreeSet::new());
+
+/// The call sites of `also_throttle`, with the time their function last ran.
+static LAST_CALLED: Mutex<BTreeMap<&'static Location<'static>, Instant>> = Mutex::new(BTreeMap::new());
+
+/// The state of a call site of [`also_once!`](crate::also_once), or
+/// of [`AlsoLimited::also_once_at`].
+#[derive(Debug, Default)]
+pub struct OnceSite {
+    called: AtomicBool,
+}
+
+impl OnceSite {
+    /// Creates the state of a call site that did not run yet.
+    pub const fn new() -> Self {
+        OnceSite { called: AtomicBool::new(false) }
+    }
+
+    /// Returns whether this is the first call, marking the site as called.
+    #[inline]
+    fn first(&self) -> bool {
+        !self.called.load(Ordering::Relaxed) && !self.called.swap(true, Ordering::Relaxed)
+    }
+}
+
+/// The state of a call site of [`also_throttle!`](crate::also_throttle),
+/// or of [`AlsoLimited::also_throttle_at`].
+#[derive(Debug, Default)]
+pub struct ThrottleSite {
+    /// When the function last ran, in nanoseconds since [`epoch`] plus
+    /// one, or zero if it never ran.
+    last_called: AtomicU64,
+}
+
+impl ThrottleSite {
+    /// Creates the state of a call site that did not run yet.
+    pub const fn new() -> Self {
+        ThrottleSite { last_called: AtomicU64::new(0) }
+    }
+
+    /// Returns whether the function is due, marking it as called now if so.
+    fn due(&self, min_interval: Duration) -> bool {
+        self.due_at(epoch().elapsed(), min_interval)
+    }
+
+    /// Like [`due`](ThrottleSite::due), at the given time since [`epoch`].
+    fn due_at(&self, now: Duration, min_interval: Duration) -> bool {
+        let now = now.as_nanos().min(u64::MAX as u128 - 1) as u64 + 1;
+        let min_interval = min_interval.as_nanos().min(u64::MAX as u128) as u64;
+        let last = self.last_called.load(Ordering::Relaxed);
+        (last == 0 || now.saturating_sub(last) >= min_interval)
+            && self.last_called.compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed).is_ok()
+    }
+}
+
+/// The instant throttled call sites measure time from.
+fn epoch() -> Instant {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    *EPOCH.get_or_init(Instant::now)
+}
+
+/// Returns whether the `also_throttle` function of the given call site is
+/// due at the given time, marking it as called then if so.
+fn throttle_due(location: &'static Location<'static>, now: Instant, min_interval: Duration) -> bool {
+    let mut last_called = LAST_CALLED.lock().unwrap_or_else(PoisonError::into_inner);
+    match last_called.get(location) {
+        Some(last) if now.saturating_duration_since(*last) < min_interval => false,
+        _ => {
+            last_called.insert(location, now);
+            true
+        }
+    }
+}
+
+thread_local! {
+    /// The state of the random number generator used by `also_sample`.
+    static SAMPLE_STATE: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish());
+}
+
+/// Seeds the random number generator deciding which calls of
+/// [`also_sample`](AlsoLimited::also_sample) on the current thread run
+/// the function, making them deterministic. Mostly useful in tests.
+///
+/// Example:
+/// ```
+/// # use apply_also::{ AlsoLimited, seed_sample };
+/// let sample = || {
+///     let mut sampled = Vec::new();
+///     for i in 0..100 {
+///         i.also_sample(0.1, |it| sampled.push(*it));
+///     }
+///     sampled
+/// };
+/// seed_sample(42);
+/// let first = sample();
+/// seed_sample(42);
+/// assert_eq!(sample(), first);
+/// ```
+pub fn seed_sample(seed: u64) {
+    SAMPLE_STATE.with(|state| state.set(seed));
+}
+
+/// Returns a random number in `[0, 1)`, using SplitMix64.
+fn next_sample() -> f64 {
+    let random = SAMPLE_STATE.with(|state| {
+        let next = state.get().wrapping_add(0x9e37_79b9_7f4a_7c15);
+        state.set(next);
+        let mixed = (next ^ (next >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        let mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        mixed ^ (mixed >> 31)
+    });
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+pub trait AlsoLimited<T> {
+    /// Applies the given function to a reference of this value, but
+    /// only the first time this call site is executed. Returns the
+    /// original value.
+    ///
+    /// Every call locks a global table of call sites. This is the slow
+    /// path: on hot paths, use [`also_once!`](crate::also_once) instead,
+    /// which only loads an atomic after the first call.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoLimited;
+    /// let mut logged = Vec::new();
+    /// for i in 0..3 {
+    ///     i.also_once(|it| logged.push(*it));
+    /// }
+    /// assert_eq!(logged, vec![0]);
+    /// ```
+    fn also_once(self, function: impl FnOnce(&T)) -> T;
+
+    /// Applies the given function to a reference of this value, but
+    /// only the first time it is called with the given site. Returns
+    /// the original value. Used by [`also_once!`](crate::also_once).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoLimited;
+    /// use apply_also::limit::OnceSite;
+    ///
+    /// static WARNED: OnceSite = OnceSite::new();
+    /// let mut warnings = 0;
+    /// for i in 0..3 {
+    ///     i.also_once_at(&WARNED, |_| warnings += 1);
+    /// }
+    /// assert_eq!(warnings, 1);
+    /// ```
+    fn also_once_at(self, site: &OnceSite, function: impl FnOnce(&T)) -> T;
+
+    /// Applies the given function to a reference of this value for a
+    /// random fraction of the calls, given by `rate` between 0 and 1.
+    /// Returns the original value.
+    ///
+    /// Use [`seed_sample`] to make the sampling deterministic.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoLimited;
+    /// let mut sampled = 0;
+    /// for i in 0..10_000 {
+    ///     i.also_sample(0.01, |_| sampled += 1);
+    /// }
+    /// assert!(sampled > 0 && sampled < 1_000);
+    /// ```
+    fn also_sample(self, rate: f64, function: impl FnOnce(&T)) -> T;
+
+    /// Applies the given function to a reference of this value, unless
+    /// it already ran at this call site within the given interval.
+    /// Returns the original value.
+    ///
+    /// Every call locks a global table of call sites. This is the slow
+    /// path: on hot paths, use [`also_throttle!`](crate::also_throttle)
+    /// instead, which only uses atomics.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoLimited;
+    /// use std::time::Duration;
+    ///
+    /// let mut logged = Vec::new();
+    /// for event in ["connected", "received", "received"] {
+    ///     event.also_throttle(Duration::from_secs(60), |it| logged.push(*it));
+    /// }
+    /// assert_eq!(logged, vec!["connected"]);
+    /// ```
+    fn also_throttle(self, min_interval: Duration, function: impl FnOnce(&T)) -> T;
+
+    /// Applies the given function to a reference of this value, unless
+    /// it already ran with the given site within the given interval.
+    /// Returns the original value. Used by
+    /// [`also_throttle!`](crate::also_throttle).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoLimited;
+    /// use apply_also::limit::ThrottleSite;
+    /// use std::time::Duration;
+    ///
+    /// static PROGRESS: ThrottleSite = ThrottleSite::new();
+    /// let mut reported = 0;
+    /// for i in 0..100 {
+    ///     i.also_throttle_at(&PROGRESS, Duration::from_secs(1), |_| reported += 1);
+    /// }
+    /// assert_eq!(reported, 1);
+    /// ```
+    fn also_throttle_at(self, site: &ThrottleSite, min_interval: Duration, function: impl FnOnce(&T)) -> T;
+}
+
+/// Applies the given function to a reference of the value, but only
+/// the first time this call site is executed. Returns the value.
+///
+/// Like [`AlsoLimited::also_once`], but the state of the call site is
+/// kept in a static, so calls after the first only load an atomic.
+///
+/// Example:
+/// ```
+/// # use apply_also::also_once;
+/// let mut logged = Vec::new();
+/// for i in 0..3 {
+///     also_once!(i, |it| logged.push(*it));
+/// }
+/// assert_eq!(logged, vec![0]);
+/// ```
+#[macro_export]
+macro_rules! also_once {
+    ($value:expr, $function:expr $(,)?) => {{
+        static SITE: $crate::limit::OnceSite = $crate::limit::OnceSite::new();
+        $crate::AlsoLimited::also_once_at($value, &SITE, $function)
+    }};
+}
+
+/// Applies the given function to a reference of the value, unless it
+/// already ran at this call site within the given interval. Returns
+/// the value.
+///
+/// Like [`AlsoLimited::also_throttle`], but the state of the call site
+/// is kept in a static, so calls only use atomics.
+///
+/// Example:
+/// ```
+/// # use apply_also::also_throttle;
+/// use std::time::Duration;
+///
+/// let mut logged = Vec::new();
+/// for event in ["connected", "received", "received"] {
+///     also_throttle!(event, Duration::from_secs(60), |it| logged.push(*it));
+/// }
+/// assert_eq!(logged, vec!["connected"]);
+/// ```
+#[macro_export]
+macro_rules! also_throttle {
+    ($value:expr, $min_interval:expr, $function:expr $(,)?) => {{
+        static SITE: $crate::limit::ThrottleSite = $crate::limit::ThrottleSite::new();
+        $crate::AlsoLimited::also_throttle_at($value, &SITE, $min_interval, $function)
+    }};
+}
+
+impl<T: ApplyAlso> AlsoLimited<T> for T {
+    #[inline(always)]
+    #[track_caller]
+    fn also_once(self, function: impl FnOnce(&T)) -> T {
+        let first = CALLED.lock().unwrap_or_else(PoisonError::into_inner).insert(Location::caller());
+        if first {
+            function(&self);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_once_at(self, site: &OnceSite, function: impl FnOnce(&T)) -> T {
+        if site.first() {
+            function(&self);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_sample(self, rate: f64, function: impl FnOnce(&T)) -> T {
+        if next_sample() < rate {
+            function(&self);
+        }
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_throttle(self, min_interval: Duration, function: impl FnOnce(&T)) -> T {
+        if throttle_due(Location::caller(), Instant::now(), min_interval) {
+            function(&self);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_throttle_at(self, site: &ThrottleSite, min_interval: Duration, function: impl FnOnce(&T)) -> T {
+        if site.due(min_interval) {
+            function(&self);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn also_once() {
+        let mut calls = Vec::new();
+        for i in 0..3 {
+            let x = i.also_once(|it| calls.push(("first", *it)));
+            assert_eq!(x, i);
+            i.also_once(|it| calls.push(("second", *it)));
+        }
+        assert_eq!(calls, vec![("first", 0), ("second", 0)]);
+    }
+
+    #[test]
+    fn also_sample() {
+        let mut sampled = 0;
+        seed_sample(7);
+        for i in 0..1000 {
+            assert_eq!(i.also_sample(0.25, |_| sampled += 1), i);
+            i.also_sample(0.0, |_| panic!("sampled at rate 0"));
+        }
+        assert!((200..300).contains(&sampled), "sampled {} of 1000", sampled);
+
+        let mut always = 0;
+        for i in 0..100 {
+            i.also_sample(1.0, |_| always += 1);
+        }
+        assert_eq!(always, 100);
+    }
+
+    #[test]
+    fn also_throttle() {
+        let mut calls = Vec::new();
+        let mut throttled = |i: i32| i.also_throttle(Duration::from_secs(3600), |it| calls.push(*it));
+        assert_eq!((0..3).map(&mut throttled).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(calls, vec![0]);
+
+        let (location, start, interval) = (Location::caller(), Instant::now(), Duration::from_millis(20));
+        let due: Vec<_> = [0, 10, 20, 39, 40]
+            .iter()
+            .map(|ms| throttle_due(location, start + Duration::from_millis(*ms), interval))
+            .collect();
+        assert_eq!(due, vec![true, false, true, false, true]);
+    }
+
+    #[test]
+    fn also_once_macro() {
+        let mut calls = Vec::new();
+        for i in 0..3 {
+            assert_eq!(also_once!(i, |it: &i32| calls.push(("first", *it))), i);
+            also_once!(i, |it: &i32| calls.push(("second", *it)));
+        }
+        assert_eq!(calls, vec![("first", 0), ("second", 0)]);
+    }
+
+    #[test]
+    fn also_throttle_macro() {
+        let mut calls = Vec::new();
+        for i in 0..3 {
+            also_throttle!(i, Duration::from_secs(3600), |it: &i32| calls.push(*it));
+        }
+        assert_eq!(calls, vec![0]);
+
+        let (site, interval) = (ThrottleSite::new(), Duration::from_millis(20));
+        let due: Vec<_> = [0, 10, 20, 39, 40]
+            .iter()
+            .map(|ms| site.due_at(Duration::from_millis(*ms), interval))
+            .collect();
+        assert_eq!(due, vec![true, false, true, false, true]);
+    }
+}
@@ -0,0 +1,233 @@
+//! `also` variants logging the value through the `log` facade.
+//!
+//! All records use the target `"apply_also"` unless another target is
+//! given, and carry the source location of the call.
+
+use crate::ApplyAlso;
+use core::fmt::{ self, Debug, Display };
+use core::panic::Location;
+use log::{ Level, Metadata, Record };
+
+pub(crate) const TARGET: &str = "apply_also";
+
+pub trait AlsoLog<T> {
+    /// Logs the `Debug` representation of this value at the given
+    /// level. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, AlsoLog };
+    /// use log::Level;
+    ///
+    /// let x = 256.apply(|it| it * 2).also_log(Level::Debug);
+    /// assert_eq!(x, 512);
+    /// ```
+    fn also_log(self, level: Level) -> T where T: Debug;
+
+    /// Logs the given message followed by the `Debug` representation
+    /// of this value at the given level. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoLog;
+    /// use log::Level;
+    ///
+    /// // logs "loaded config: [1, 2]"
+    /// let x = vec![1, 2].also_log_with(Level::Info, "loaded config");
+    /// assert_eq!(x, vec![1, 2]);
+    /// ```
+    fn also_log_with(self, level: Level, message: &str) -> T where T: Debug;
+
+    /// Logs the `Debug` representation of this value at the given
+    /// level, using the given target. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoLog;
+    /// use log::Level;
+    ///
+    /// let x = 3.also_log_target(Level::Trace, "my_app::pipeline");
+    /// assert_eq!(x, 3);
+    /// ```
+    fn also_log_target(self, level: Level, target: &str) -> T where T: Debug;
+
+    /// Logs the `Display` representation of this value at the given
+    /// level. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoLog;
+    /// use log::Level;
+    ///
+    /// let x = "ready".also_log_display(Level::Info);
+    /// assert_eq!(x, "ready");
+    /// ```
+    fn also_log_display(self, level: Level) -> T where T: Display;
+
+    /// Shorthand for `also_log(Level::Debug)`.
+    fn also_debug(self) -> T where T: Debug;
+
+    /// Shorthand for `also_log(Level::Info)`.
+    fn also_info(self) -> T where T: Debug;
+
+    /// Shorthand for `also_log(Level::Warn)`.
+    fn also_warn(self) -> T where T: Debug;
+
+    /// Shorthand for `also_log(Level::Error)`.
+    fn also_error(self) -> T where T: Debug;
+}
+
+#[inline]
+pub(crate) fn log(level: Level, target: &str, location: &'static Location<'static>, args: fmt::Arguments<'_>) {
+    // Same checks as the `log!` macros, so `max_level_*` and
+    // `release_max_level_*` features are respected.
+    if level > log::STATIC_MAX_LEVEL || level > log::max_level() {
+        return;
+    }
+    let logger = log::logger();
+    if logger.enabled(&Metadata::builder().level(level).target(target).build()) {
+        logger.log(
+            &Record::builder()
+                .level(level)
+                .target(target)
+                .file_static(Some(location.file()))
+                .line(Some(location.line()))
+                .args(args)
+                .build(),
+        );
+    }
+}
+
+impl<T: ApplyAlso> AlsoLog<T> for T {
+    #[inline(always)]
+    #[track_caller]
+    fn also_log(self, level: Level) -> T where T: Debug {
+        log(level, TARGET, Location::caller(), format_args!("{:?}", self));
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_log_with(self, level: Level, message: &str) -> T where T: Debug {
+        log(level, TARGET, Location::caller(), format_args!("{}: {:?}", message, self));
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_log_target(self, level: Level, target: &str) -> T where T: Debug {
+        log(level, target, Location::caller(), format_args!("{:?}", self));
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_log_display(self, level: Level) -> T where T: Display {
+        log(level, TARGET, Location::caller(), format_args!("{}", self));
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_debug(self) -> T where T: Debug {
+        self.also_log(Level::Debug)
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_info(self) -> T where T: Debug {
+        self.also_log(Level::Info)
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_warn(self) -> T where T: Debug {
+        self.also_log(Level::Warn)
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_error(self) -> T where T: Debug {
+        self.also_log(Level::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::{ LevelFilter, Log };
+    use std::sync::{ Mutex, Once };
+
+    struct TestLogger;
+
+    static RECORDS: Mutex<Vec<(Level, String, String, u32)>> = Mutex::new(Vec::new());
+
+    /// Held by tests depending on the maximum level, which is global.
+    static MAX_LEVEL: Mutex<()> = Mutex::new(());
+
+    impl Log for TestLogger {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            RECORDS.lock().unwrap().push((
+                record.level(),
+                record.target().to_string(),
+                record.args().to_string(),
+                record.line().unwrap(),
+            ));
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Returns the records logged so far containing the given marker.
+    fn records(marker: &str) -> Vec<(Level, String, String, u32)> {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&TestLogger).unwrap();
+            log::set_max_level(LevelFilter::Trace);
+        });
+        RECORDS.lock().unwrap().iter().filter(|r| r.2.contains(marker)).cloned().collect()
+    }
+
+    #[test]
+    fn also_log() {
+        let _lock = MAX_LEVEL.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        records("");
+        let line = line!() + 1;
+        let x = "also_log".also_log(Level::Info).also_warn();
+        assert_eq!(x, "also_log");
+        assert_eq!(records("also_log"), vec![
+            (Level::Info, TARGET.to_string(), "\"also_log\"".to_string(), line),
+            (Level::Warn, TARGET.to_string(), "\"also_log\"".to_string(), line),
+        ]);
+    }
+
+    #[test]
+    fn also_log_with() {
+        let _lock = MAX_LEVEL.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        records("");
+        "with".also_log_with(Level::Debug, "prefix").also_log_display(Level::Error);
+        7357.also_log_target(Level::Trace, "custom");
+
+        let logged: Vec<_> = records("with").into_iter().map(|r| (r.0, r.2)).collect();
+        assert_eq!(logged, vec![
+            (Level::Debug, "prefix: \"with\"".to_string()),
+            (Level::Error, "with".to_string()),
+        ]);
+        assert_eq!(records("7357")[0].1, "custom");
+    }
+
+    #[test]
+    fn max_level() {
+        let _lock = MAX_LEVEL.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        records("");
+        log::set_max_level(LevelFilter::Warn);
+        "max_level".also_info().also_error();
+        log::set_max_level(LevelFilter::Trace);
+        let levels: Vec<_> = records("max_level").into_iter().map(|r| r.0).collect();
+        assert_eq!(levels, vec![Level::Error]);
+    }
+}
@@ -0,0 +1,354 @@
+//! Macros built on top of the `apply` and `also` traits.
+
+/// Implements the [`ApplyAlso`](crate::ApplyAlso) marker for the given
+/// types, opting them in to the extension traits when the `blanket`
+/// feature is disabled. With `blanket`, every type implements the
+/// marker already, and the macro expands to nothing. Generic types list
+/// their parameters first, as in `impl_apply!(impl<T> Wrapper<T>)`.
+/// Due to the orphan rules, only types of the current crate can be
+/// opted in.
+///
+/// Example:
+/// ```
+/// use apply_also::{ impl_apply, Apply };
+///
+/// struct Meters(f64);
+/// struct Wrapper<T>(T);
+///
+/// impl_apply!(Meters);
+/// impl_apply!(impl<T> Wrapper<T>);
+///
+/// let x = Meters(2.0).apply(|it| it.0 * 100.0);
+/// assert_eq!(x, 200.0);
+/// assert_eq!(Wrapper(2).apply(|it| it.0), 2);
+/// ```
+#[macro_export]
+macro_rules! impl_apply {
+    (impl<$($parameter:ident),* $(,)?> $ty:ty) => {
+        $crate::__impl_apply!([$($parameter),*] $ty);
+    };
+    ($($ty:ty),+ $(,)?) => {
+        $($crate::__impl_apply!([] $ty);)+
+    };
+}
+
+// Selected when this crate is compiled, so `impl_apply!` keeps compiling
+// once any crate in the dependency graph enables `blanket`.
+#[cfg(not(any(feature = "blanket", test)))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_apply {
+    ([$($parameter:ident),*] $ty:ty) => {
+        impl<$($parameter),*> $crate::ApplyAlso for $ty {}
+    };
+}
+
+#[cfg(any(feature = "blanket", test))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_apply {
+    ([$($parameter:ident),*] $ty:ty) => {};
+}
+
+/// Applies a sequence of functions to a value, from left to right.
+///
+/// `pipe!(value => f => g)` expands to `value.apply(f).apply(g)`.
+///
+/// Example:
+/// ```
+/// # use apply_also::pipe;
+/// fn double(x: i32) -> i32 { x * 2 }
+///
+/// let x = pipe!(5 => double => |it| format!("{}!", it));
+/// assert_eq!(x, "10!");
+/// ```
+#[macro_export]
+macro_rules! pipe {
+    ($value:expr) => {
+        $value
+    };
+    ($value:expr => $function:expr $(=> $rest:expr)*) => {
+        $crate::pipe!($crate::Apply::apply($value, $function) $(=> $rest)*)
+    };
+}
+
+/// Composes functions from left to right into a single function.
+///
+/// `compose!(f, g, h)` returns a function equivalent to
+/// `move |it| h(g(f(it)))`, which can be passed to `apply`.
+///
+/// Example:
+/// ```
+/// # use apply_also::{ compose, Apply };
+/// fn double(x: i32) -> i32 { x * 2 }
+///
+/// let describe = compose!(str::len, |it| it as i32, double, |it| format!("{}!", it));
+/// assert_eq!("abc".apply(describe), "6!");
+/// ```
+#[macro_export]
+macro_rules! compose {
+    ($function:expr $(,)?) => {
+        $function
+    };
+    ($first:expr, $($rest:expr),+ $(,)?) => {{
+        let first = $first;
+        let rest = $crate::compose!($($rest),+);
+        move |value| rest(first(value))
+    }};
+}
+
+/// Creates a closure calling a function with some of its arguments
+/// given, and `_` holes for the others, which become the closure's
+/// parameters in order.
+///
+/// `partial!(f(a, _, b, _))` expands to `|x, y| f(a, x, b, y)`. The
+/// given arguments are evaluated on each call. `partial!(move f(a, _))`
+/// creates a `move` closure.
+///
+/// Example:
+/// ```
+/// # use apply_also::{ partial, Also, Apply };
+/// fn insert_into(map: &mut Vec<(String, i32)>, key: &str, value: i32) {
+///     map.push((key.to_string(), value));
+/// }
+///
+/// let mut map = Vec::new();
+/// let x = 3.also(|it| insert_into(&mut map, "x", *it)).apply(partial!(i32::pow(_, 2)));
+/// vec![1, 2].into_iter().for_each(partial!(insert_into(&mut map, "y", _)));
+/// assert_eq!(x, 9);
+/// assert_eq!(map.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! partial {
+    (move $($function:ident)::+ ( $($arguments:tt)* )) => {
+        $crate::__partial!(@parse [move] [$($function)::+] [] [] $($arguments)*)
+    };
+    ($($function:ident)::+ ( $($arguments:tt)* )) => {
+        $crate::__partial!(@parse [] [$($function)::+] [] [] $($arguments)*)
+    };
+}
+
+// Each expansion introduces its own `hole`, so the holes are distinct
+// parameters despite sharing a name.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __partial {
+    (@parse [$($move:tt)?] [$($function:tt)*] [$($parameters:ident)*] [$($arguments:tt)*]) => {
+        $($move)? |$($parameters),*| $($function)*($($arguments)*)
+    };
+    (@parse [$($move:tt)?] [$($function:tt)*] [$($parameters:ident)*] [$($arguments:tt)*] _ $(, $($rest:tt)*)?) => {
+        $crate::__partial!(@parse [$($move)?] [$($function)*] [$($parameters)* hole] [$($arguments)* hole,] $($($rest)*)?)
+    };
+    (@parse [$($move:tt)?] [$($function:tt)*] [$($parameters:ident)*] [$($arguments:tt)*] $argument:expr $(, $($rest:tt)*)?) => {
+        $crate::__partial!(@parse [$($move)?] [$($function)*] [$($parameters)*] [$($arguments)* $argument,] $($($rest)*)?)
+    };
+}
+
+/// Calls a sequence of methods on a value and returns the value,
+/// like method cascades in Dart.
+///
+/// `cascade!(value => { a(x); b(); })` expands to
+/// `value.also_mut(|it| { it.a(x); it.b(); })`.
+///
+/// Example:
+/// ```
+/// # use apply_also::cascade;
+/// let x = cascade!(Vec::new() => {
+///     push(3);
+///     push(1);
+///     push(2);
+///     sort();
+/// });
+/// assert_eq!(x, vec![1, 2, 3]);
+/// ```
+#[macro_export]
+macro_rules! cascade {
+    ($value:expr => {}) => {
+        $value
+    };
+    ($value:expr => { $($method:ident ( $($argument:expr),* $(,)? );)* }) => {
+        $crate::Also::also_mut($value, |it| {
+            $( it.$method($($argument),*); )*
+        })
+    };
+}
+
+/// Runs a block with mutable access to a value and returns the value,
+/// similar to Kotlin's `with(receiver) { ... }`.
+///
+/// Unlike `also_mut`, the block is not a closure, so `?`, `return`
+/// and `break` apply to the surrounding function or loop.
+///
+/// Example:
+/// ```
+/// # use apply_also::with;
+/// # use std::num::ParseIntError;
+/// #[derive(Default)]
+/// struct Config { retries: u32, verbose: bool }
+///
+/// fn parse(retries: &str) -> Result<Config, ParseIntError> {
+///     Ok(with!(Config::default(), it => {
+///         it.retries = retries.parse()?;
+///         it.verbose = true;
+///     }))
+/// }
+///
+/// assert_eq!(parse("3").unwrap().retries, 3);
+/// assert!(parse("three").is_err());
+/// ```
+#[macro_export]
+macro_rules! with {
+    ($value:expr, $it:ident => $body:block) => {{
+        let mut $it = $value;
+        {
+            let $it = &mut $it;
+            $body;
+        }
+        $it
+    }};
+}
+
+/// Runs a block with mutable access to a value and returns the
+/// value of the block. Like [`with!`], the block is not a closure.
+///
+/// Example:
+/// ```
+/// # use apply_also::with_run;
+/// let len = with_run!(vec![1, 2], it => {
+///     it.push(3);
+///     it.len()
+/// });
+/// assert_eq!(len, 3);
+/// ```
+#[macro_export]
+macro_rules! with_run {
+    ($value:expr, $it:ident => $body:block) => {{
+        let mut $it = $value;
+        let $it = &mut $it;
+        $body
+    }};
+}
+
+/// Asserts that a value matches a pattern, with an optional guard,
+/// and returns the value. Panics with the value's `Debug` output
+/// otherwise.
+///
+/// The pattern is matched against the value itself, so bindings of
+/// non-`Copy` values need `ref`, e.g. `Some(ref name)`.
+///
+/// Example:
+/// ```
+/// # use apply_also::{ also_matches, Apply };
+/// let x = also_matches!("42".parse::<i32>().ok(), Some(x) if x > 3);
+/// assert_eq!(x, Some(42));
+///
+/// let name = also_matches!(Some(String::from("apply")), Some(ref name) if !name.is_empty());
+/// assert_eq!(name.apply(Option::unwrap), "apply");
+/// ```
+#[macro_export]
+macro_rules! also_matches {
+    ($value:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        match $value {
+            value => {
+                if !::core::matches!(value, $pattern $(if $guard)?) {
+                    ::core::panic!(
+                        "assertion failed: value matches `{}`\n value: {:?}",
+                        ::core::stringify!($pattern $(if $guard)?),
+                        value,
+                    );
+                }
+                value
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn pipe() {
+        assert_eq!(pipe!(3), 3);
+        assert_eq!(pipe!(3 => |it| it + 1), 4);
+
+        let x = pipe!(vec![3, 1, 2] => |mut it: Vec<i32>| { it.sort(); it } => |it| it[0]);
+        assert_eq!(x, 1);
+    }
+
+    #[test]
+    fn compose() {
+        let suffix = String::from("!");
+        let f = compose!(|it: i32| it + 1, |it: i32| it.to_string(), move |it: String| it + &suffix,);
+        assert_eq!(f(1), "2!");
+        assert_eq!(compose!(i32::abs)(-3), 3);
+    }
+
+    #[test]
+    fn partial() {
+        fn between(low: i32, value: i32, high: i32) -> bool {
+            low <= value && value <= high
+        }
+
+        fn concat(a: String, b: &str) -> String {
+            a + b
+        }
+
+        let in_range: Vec<i32> = (0..10).filter(|it| partial!(between(2, _, 4))(*it)).collect();
+        assert_eq!(in_range, vec![2, 3, 4]);
+        assert!(partial!(between(_, 5, _))(1, 9));
+        assert_eq!(partial!(std::cmp::max(1, 2))(), 2);
+
+        let prefix = String::from("x");
+        let join = partial!(move concat(prefix.clone(), _));
+        assert_eq!(join("y"), "xy");
+    }
+
+    #[test]
+    fn cascade() {
+        use std::collections::HashMap;
+
+        let scale = 10;
+        let map = cascade!(HashMap::new() => {
+            insert("a", scale + 1);
+            insert("b", 2 * scale,);
+            remove("a");
+        });
+        assert_eq!(map.get("b"), Some(&20));
+        assert_eq!(map.len(), 1);
+
+        let empty: Vec<i32> = cascade!(Vec::new() => {});
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn with() {
+        fn first_even(values: &[i32]) -> Option<Vec<i32>> {
+            Some(with!(Vec::new(), it => {
+                it.push(*values.iter().find(|v| *v % 2 == 0)?);
+            }))
+        }
+
+        assert_eq!(first_even(&[1, 4, 6]), Some(vec![4]));
+        assert_eq!(first_even(&[1, 3]), None);
+    }
+
+    #[test]
+    fn with_run() {
+        let x = with_run!(String::from("a"), s => {
+            s.push('b');
+            s.len()
+        });
+        assert_eq!(x, 2);
+    }
+
+    #[test]
+    fn also_matches() {
+        assert_eq!(also_matches!(Ok::<_, ()>(3), Ok(1..=5)), Ok(3));
+        assert_eq!(also_matches!(vec![1, 2], ref v if v.len() == 2,), vec![1, 2]);
+
+        let payload = std::panic::catch_unwind(|| also_matches!(Some(2), Some(x) if x > 3)).unwrap_err();
+        assert_eq!(
+            payload.downcast_ref::<String>().unwrap(),
+            "assertion failed: value matches `Some(x) if x > 3`\n value: Some(2)",
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! `also` variants for maps, built on their entry APIs.
+//!
+//! [`MapAlsoExt`] is implemented for `BTreeMap` and, with the `std`
+//! feature, for `HashMap`. Other map types can implement it as well.
+
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use core::hash::{ BuildHasher, Hash };
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+pub trait MapAlsoExt<K, V> {
+    /// Inserts the given default value for the key if it is missing,
+    /// then applies the given function to a mutable reference of the
+    /// value for the key. Returns a mutable reference to the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::MapAlsoExt;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut words: HashMap<char, Vec<&str>> = HashMap::new();
+    /// for word in ["apply", "also", "tee"] {
+    ///     let first = word.chars().next().unwrap();
+    ///     words.entry_also(first, Vec::new(), |it| it.push(word));
+    /// }
+    /// assert_eq!(words[&'a'], vec!["apply", "also"]);
+    /// ```
+    fn entry_also(&mut self, key: K, default: V, function: impl FnOnce(&mut V)) -> &mut V;
+}
+
+impl<K: Ord, V> MapAlsoExt<K, V> for BTreeMap<K, V> {
+    #[inline(always)]
+    fn entry_also(&mut self, key: K, default: V, function: impl FnOnce(&mut V)) -> &mut V {
+        let value = self.entry(key).or_insert(default);
+        function(value);
+        value
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V, S: BuildHasher> MapAlsoExt<K, V> for HashMap<K, V, S> {
+    #[inline(always)]
+    fn entry_also(&mut self, key: K, default: V, function: impl FnOnce(&mut V)) -> &mut V {
+        let value = self.entry(key).or_insert(default);
+        function(value);
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_also_btree_map() {
+        let mut counts = BTreeMap::new();
+        for word in ["a", "b", "a"] {
+            counts.entry_also(word, 0, |it| *it += 1);
+        }
+        assert_eq!(counts.into_iter().collect::<Vec<_>>(), vec![("a", 2), ("b", 1)]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn entry_also_hash_map() {
+        let mut map = HashMap::new();
+        *map.entry_also("x", vec![1], |it| it.push(2)) = vec![0];
+        map.entry_also("x", vec![9], |it| it.push(3));
+        assert_eq!(map["x"], vec![0, 3]);
+    }
+}
@@ -0,0 +1,126 @@
+//! `also` variants recording metrics through the `metrics` facade.
+
+use crate::ApplyAlso;
+
+pub trait AlsoMetrics<T> {
+    /// Increments the counter with the given name by one.
+    /// Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, AlsoMetrics };
+    /// let x = 256.apply(|it| it * 2).also_count("pipeline.items");
+    /// assert_eq!(x, 512);
+    /// ```
+    fn also_count(self, name: &'static str) -> T;
+
+    /// Sets the gauge with the given name to the result of the given
+    /// function, applied to a reference of this value.
+    /// Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoMetrics;
+    /// let queue = vec![1, 2, 3].also_gauge("queue.len", |it| it.len() as f64);
+    /// assert_eq!(queue.len(), 3);
+    /// ```
+    fn also_gauge(self, name: &'static str, function: impl FnOnce(&T) -> f64) -> T;
+}
+
+impl<T: ApplyAlso> AlsoMetrics<T> for T {
+    #[inline(always)]
+    fn also_count(self, name: &'static str) -> T {
+        metrics::counter!(name).increment(1);
+        self
+    }
+
+    #[inline(always)]
+    fn also_gauge(self, name: &'static str, function: impl FnOnce(&T) -> f64) -> T {
+        metrics::gauge!(name).set(function(&self));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics::{ Counter, CounterFn, Gauge, GaugeFn, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit };
+    use std::sync::{ Arc, Mutex };
+
+    type Events = Arc<Mutex<Vec<(String, f64)>>>;
+
+    #[derive(Default)]
+    struct TestRecorder {
+        events: Events,
+    }
+
+    struct Handle {
+        name: String,
+        events: Events,
+    }
+
+    impl CounterFn for Handle {
+        fn increment(&self, value: u64) {
+            self.events.lock().unwrap().push((format!("{} +", self.name), value as f64));
+        }
+
+        fn absolute(&self, value: u64) {
+            self.events.lock().unwrap().push((format!("{} =", self.name), value as f64));
+        }
+    }
+
+    impl GaugeFn for Handle {
+        fn increment(&self, value: f64) {
+            self.events.lock().unwrap().push((format!("{} +", self.name), value));
+        }
+
+        fn decrement(&self, value: f64) {
+            self.events.lock().unwrap().push((format!("{} -", self.name), value));
+        }
+
+        fn set(&self, value: f64) {
+            self.events.lock().unwrap().push((format!("{} =", self.name), value));
+        }
+    }
+
+    impl TestRecorder {
+        fn handle(&self, key: &Key) -> Arc<Handle> {
+            Arc::new(Handle { name: key.name().to_string(), events: self.events.clone() })
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.handle(key))
+        }
+
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::from_arc(self.handle(key))
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn also_count_and_gauge() {
+        let recorder = TestRecorder::default();
+        let x = metrics::with_local_recorder(&recorder, || {
+            vec![1, 2]
+                .also_count("items")
+                .also_gauge("len", |it| it.len() as f64)
+                .also_count("items")
+        });
+        assert_eq!(x, vec![1, 2]);
+        assert_eq!(*recorder.events.lock().unwrap(), vec![
+            ("items +".to_string(), 1.0),
+            ("len =".to_string(), 2.0),
+            ("items +".to_string(), 1.0),
+        ]);
+    }
+}
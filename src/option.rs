@@ -0,0 +1,187 @@
+//! `apply` and `also` variants for `Option`.
+
+use crate::init_with;
+
+pub trait OptionApplyExt<T> {
+    /// Applies the given function to the contained value, if any,
+    /// and returns the result. Similar to Kotlin's `?.let`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::OptionApplyExt;
+    /// let x = Some(256).let_some(|it| it * 2);
+    /// assert_eq!(x, Some(512));
+    /// ```
+    fn let_some<R>(self, function: impl FnOnce(T) -> R) -> Option<R>;
+
+    /// Applies the given function to a reference of the contained
+    /// value, if any. Returns the original option.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::OptionApplyExt;
+    /// let x = Some(3).also_some(|it| {
+    ///     println!("found {}", it);
+    /// });
+    /// assert_eq!(x, Some(3));
+    /// ```
+    fn also_some(self, function: impl FnOnce(&T)) -> Option<T>;
+
+    /// Calls the given function if this option is `None`.
+    /// Returns the original option.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::OptionApplyExt;
+    /// let x: Option<i32> = None.also_none(|| {
+    ///     println!("nothing found");
+    /// });
+    /// assert_eq!(x, None);
+    /// ```
+    fn also_none(self, function: impl FnOnce()) -> Option<T>;
+
+    /// Applies the given function to the contained value, if any,
+    /// and returns the result. Equivalent to `Option::and_then`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::OptionApplyExt;
+    /// let x = Some("256").and_apply(|it| it.parse::<i32>().ok());
+    /// assert_eq!(x, Some(256));
+    /// ```
+    fn and_apply<R>(self, function: impl FnOnce(T) -> Option<R>) -> Option<R>;
+
+    /// Applies the given function to a reference of the contained
+    /// value, if any, and returns the result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::OptionApplyExt;
+    /// let x = Some(vec![1, 2, 3]).and_apply_ref(|it| it.first().copied());
+    /// assert_eq!(x, Some(1));
+    /// ```
+    fn and_apply_ref<R>(self, function: impl FnOnce(&T) -> Option<R>) -> Option<R>;
+
+    /// Inserts the given default value if this option is `None`, and
+    /// applies the given function to a mutable reference of it, to
+    /// configure the new value. Returns a mutable reference to the
+    /// contained value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::OptionApplyExt;
+    /// let mut args: Option<Vec<&str>> = None;
+    /// args.get_or_insert_also(Vec::new(), |it| it.push("--verbose")).push("run");
+    /// args.get_or_insert_also(Vec::new(), |it| it.push("--verbose")).push("again");
+    /// assert_eq!(args, Some(vec!["--verbose", "run", "again"]));
+    /// ```
+    fn get_or_insert_also(&mut self, default: T, function: impl FnOnce(&mut T)) -> &mut T;
+
+    /// Like [`get_or_insert_also`](OptionApplyExt::get_or_insert_also),
+    /// but only creates the value with the given constructor if it is
+    /// actually inserted.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::OptionApplyExt;
+    /// let mut cache: Option<String> = None;
+    /// let value = cache.also_insert_with(String::new, |it| it.push_str("warm"));
+    /// assert_eq!(value, "warm");
+    /// ```
+    fn also_insert_with(&mut self, constructor: impl FnOnce() -> T, function: impl FnOnce(&mut T)) -> &mut T;
+}
+
+impl<T> OptionApplyExt<T> for Option<T> {
+    #[inline(always)]
+    fn let_some<R>(self, function: impl FnOnce(T) -> R) -> Option<R> {
+        self.map(function)
+    }
+
+    #[inline(always)]
+    fn also_some(self, function: impl FnOnce(&T)) -> Option<T> {
+        if let Some(value) = &self {
+            function(value);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_none(self, function: impl FnOnce()) -> Option<T> {
+        if self.is_none() {
+            function();
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn and_apply<R>(self, function: impl FnOnce(T) -> Option<R>) -> Option<R> {
+        self.and_then(function)
+    }
+
+    #[inline(always)]
+    fn and_apply_ref<R>(self, function: impl FnOnce(&T) -> Option<R>) -> Option<R> {
+        self.and_then(|value| function(&value))
+    }
+
+    #[inline(always)]
+    fn get_or_insert_also(&mut self, default: T, function: impl FnOnce(&mut T)) -> &mut T {
+        self.also_insert_with(|| default, function)
+    }
+
+    #[inline(always)]
+    fn also_insert_with(&mut self, constructor: impl FnOnce() -> T, function: impl FnOnce(&mut T)) -> &mut T {
+        match self {
+            Some(value) => value,
+            None => self.insert(init_with(constructor, function)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn let_some() {
+        assert_eq!(Some("5").let_some(str::len), Some(1));
+        assert_eq!(None::<&str>.let_some(str::len), None);
+    }
+
+    #[test]
+    fn also_some() {
+        let mut seen = Vec::new();
+        let x = Some(1)
+            .also_some(|it| seen.push(*it))
+            .also_none(|| seen.push(0));
+        assert_eq!(x, Some(1));
+
+        let y = None
+            .also_some(|it| seen.push(*it))
+            .also_none(|| seen.push(0));
+        assert_eq!(y, None);
+        assert_eq!(seen, vec![1, 0]);
+    }
+
+    #[test]
+    fn and_apply() {
+        assert_eq!(Some(4u32).and_apply(|it| it.checked_sub(5)), None);
+        assert_eq!(Some(vec![1]).and_apply_ref(|it| it.last().copied()), Some(1));
+        assert_eq!(None::<i32>.and_apply(Some), None);
+    }
+
+    #[test]
+    fn get_or_insert_also() {
+        let mut configured = 0;
+        let mut x = None;
+        *x.get_or_insert_also(1, |it| {
+            configured += 1;
+            *it *= 10;
+        }) += 1;
+        assert_eq!(x, Some(11));
+        x.get_or_insert_also(2, |_| configured += 1);
+        assert_eq!((x, configured), (Some(11), 1));
+
+        let mut y = Some(3);
+        assert_eq!(*y.also_insert_with(|| unreachable!(), |_| unreachable!()), 3);
+    }
+}
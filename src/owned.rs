@@ -0,0 +1,143 @@
+//! Owning projections, for borrows of a value that is still needed.
+//!
+//! An [`Owned`] keeps a value together with a projection to a part of
+//! it, such as a field or a slice. Instead of storing a reference into
+//! the value, which would make it self-referential, the projection is
+//! stored as a function pointer and applied whenever the part is
+//! accessed. The value can only be accessed immutably while it is
+//! owned, so no `unsafe` code is needed and the projection always sees
+//! the same value.
+//!
+//! ```
+//! use apply_also::ApplyOwned;
+//! use apply_also::owned::Owned;
+//!
+//! struct Request { method: String, path: String }
+//!
+//! fn path(request: Request) -> Owned<Request, str> {
+//!     request.apply_owned(|it| it.path.trim_start_matches('/'))
+//! }
+//!
+//! let path = path(Request { method: "GET".into(), path: "/index.html".into() });
+//! assert_eq!(&*path, "index.html");
+//! assert_eq!(path.owner().method, "GET");
+//! ```
+
+use crate::ApplyAlso;
+use core::fmt;
+use core::ops::Deref;
+
+/// A value owned together with a projection to a part of it, created
+/// by [`ApplyOwned::apply_owned`].
+///
+/// The owned projection dereferences to the part.
+pub struct Owned<T, U: ?Sized> {
+    owner: T,
+    project: fn(&T) -> &U,
+}
+
+impl<T, U: ?Sized> Owned<T, U> {
+    /// Creates an owned projection of the given value.
+    #[inline(always)]
+    pub fn new(owner: T, project: fn(&T) -> &U) -> Self {
+        Owned { owner, project }
+    }
+
+    /// Returns the projected part of the value.
+    #[inline(always)]
+    pub fn get(&self) -> &U {
+        (self.project)(&self.owner)
+    }
+
+    /// Returns the whole value.
+    #[inline(always)]
+    pub fn owner(&self) -> &T {
+        &self.owner
+    }
+
+    /// Returns the whole value, dropping the projection.
+    #[inline(always)]
+    pub fn into_owner(self) -> T {
+        self.owner
+    }
+}
+
+impl<T, U: ?Sized> Deref for Owned<T, U> {
+    type Target = U;
+
+    #[inline(always)]
+    fn deref(&self) -> &U {
+        self.get()
+    }
+}
+
+impl<T, U: ?Sized> AsRef<U> for Owned<T, U> {
+    #[inline(always)]
+    fn as_ref(&self) -> &U {
+        self.get()
+    }
+}
+
+impl<T: Clone, U: ?Sized> Clone for Owned<T, U> {
+    fn clone(&self) -> Self {
+        Owned { owner: self.owner.clone(), project: self.project }
+    }
+}
+
+impl<T, U: ?Sized + fmt::Debug> fmt::Debug for Owned<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Owned").field(&self.get()).finish()
+    }
+}
+
+impl<T, U: ?Sized + fmt::Display> fmt::Display for Owned<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+pub trait ApplyOwned<T> {
+    /// Pairs this value with the given projection to a part of it.
+    /// The returned [`Owned`] owns the value and dereferences to the
+    /// part. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyOwned;
+    /// let line = String::from("  trimmed  ").apply_owned(|it| it.trim());
+    /// assert_eq!(line.len(), 7);
+    /// assert_eq!(line.into_owner(), "  trimmed  ");
+    /// ```
+    fn apply_owned<U: ?Sized>(self, project: fn(&T) -> &U) -> Owned<T, U>;
+}
+
+impl<T: ApplyAlso> ApplyOwned<T> for T {
+    #[inline(always)]
+    fn apply_owned<U: ?Sized>(self, project: fn(&T) -> &U) -> Owned<T, U> {
+        Owned::new(self, project)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_owned() {
+        let tail = vec![1, 2, 3].apply_owned(|it| &it[1..]);
+        assert_eq!(&*tail, &[2, 3]);
+        assert_eq!(tail.owner(), &vec![1, 2, 3]);
+        assert_eq!(tail.clone().get(), &[2, 3]);
+        assert_eq!(format!("{:?}", tail), "Owned([2, 3])");
+        assert_eq!(tail.into_owner(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn moves_with_owner() {
+        let names: Vec<Owned<String, str>> = ["a b", "c d"]
+            .iter()
+            .map(|it| it.to_string().apply_owned(|it| it.split(' ').next_back().unwrap()))
+            .collect();
+        assert_eq!(names.iter().map(|it| it.to_string()).collect::<Vec<_>>(), ["b", "d"]);
+    }
+}
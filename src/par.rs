@@ -0,0 +1,96 @@
+//! `apply` and `also` variants for collections, running the function
+//! on every item in parallel using `rayon`.
+
+use rayon::prelude::*;
+
+pub trait ParApplyExt<T> {
+    /// The type of the items passed to
+    /// [`apply_par_map`](ParApplyExt::apply_par_map).
+    type Item;
+
+    /// Applies the given function to a reference of every item in
+    /// parallel. Returns the original collection.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ParApplyExt;
+    /// let x = (1..=1000).collect::<Vec<u64>>().also_par_each(|it| {
+    ///     assert!(*it > 0);
+    /// });
+    /// assert_eq!(x.len(), 1000);
+    /// ```
+    fn also_par_each(self, function: impl Fn(&T) + Sync + Send) -> Self;
+
+    /// Applies the given function to every item in parallel and
+    /// returns the results, in the original order.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ParApplyExt;
+    /// let x = vec![1, 2, 3].apply_par_map(|it| it * 2);
+    /// assert_eq!(x, vec![2, 4, 6]);
+    ///
+    /// let lengths = ["a", "bc"].as_ref().apply_par_map(|it| it.len());
+    /// assert_eq!(lengths, vec![1, 2]);
+    /// ```
+    fn apply_par_map<U: Send>(self, function: impl Fn(Self::Item) -> U + Sync + Send) -> Vec<U>;
+}
+
+impl<T: Send + Sync> ParApplyExt<T> for Vec<T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn also_par_each(self, function: impl Fn(&T) + Sync + Send) -> Self {
+        self.par_iter().for_each(function);
+        self
+    }
+
+    #[inline(always)]
+    fn apply_par_map<U: Send>(self, function: impl Fn(T) -> U + Sync + Send) -> Vec<U> {
+        self.into_par_iter().map(function).collect()
+    }
+}
+
+impl<'a, T: Sync> ParApplyExt<T> for &'a [T] {
+    type Item = &'a T;
+
+    #[inline(always)]
+    fn also_par_each(self, function: impl Fn(&T) + Sync + Send) -> Self {
+        self.par_iter().for_each(function);
+        self
+    }
+
+    #[inline(always)]
+    fn apply_par_map<U: Send>(self, function: impl Fn(&'a T) -> U + Sync + Send) -> Vec<U> {
+        self.par_iter().map(function).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{ AtomicUsize, Ordering };
+
+    #[test]
+    fn also_par_each() {
+        let sum = AtomicUsize::new(0);
+        let x = (1..=100).collect::<Vec<usize>>().also_par_each(|it| {
+            sum.fetch_add(*it, Ordering::Relaxed);
+        });
+        assert_eq!(x.len(), 100);
+        assert_eq!(sum.into_inner(), 5050);
+
+        let count = AtomicUsize::new(0);
+        let slice = x.as_slice().also_par_each(|_| {
+            count.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!((slice.len(), count.into_inner()), (100, 100));
+    }
+
+    #[test]
+    fn apply_par_map() {
+        let x: Vec<String> = (0..50).collect::<Vec<i32>>().apply_par_map(|it| it.to_string());
+        assert_eq!(x[49], "49");
+        assert_eq!(x.as_slice().apply_par_map(String::len)[10], 2);
+    }
+}
@@ -0,0 +1,66 @@
+//! `apply` and `also` variants parsing string slices.
+
+use core::str::FromStr;
+
+pub trait ParseApplyExt {
+    /// Parses this string, applies the given function to the parsed
+    /// value and returns the result, or the parse error.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ParseApplyExt;
+    /// let x = "256".apply_parse(|it: i32| it * 2);
+    /// assert_eq!(x, Ok(512));
+    ///
+    /// let y = String::from("x").apply_parse(|it: i32| it * 2);
+    /// assert!(y.is_err());
+    /// ```
+    fn apply_parse<T: FromStr, R>(&self, function: impl FnOnce(T) -> R) -> Result<R, T::Err>;
+
+    /// Parses this string and applies the given function to a
+    /// reference of the parsed value. Returns the parsed value,
+    /// or the parse error.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ParseApplyExt;
+    /// let port = "8080".parse_also(|it: &u16| {
+    ///     println!("listening on {}", it);
+    /// });
+    /// assert_eq!(port, Ok(8080));
+    /// ```
+    fn parse_also<T: FromStr>(&self, function: impl FnOnce(&T)) -> Result<T, T::Err>;
+}
+
+impl ParseApplyExt for str {
+    #[inline(always)]
+    fn apply_parse<T: FromStr, R>(&self, function: impl FnOnce(T) -> R) -> Result<R, T::Err> {
+        self.parse().map(function)
+    }
+
+    #[inline(always)]
+    fn parse_also<T: FromStr>(&self, function: impl FnOnce(&T)) -> Result<T, T::Err> {
+        let value = self.parse()?;
+        function(&value);
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_parse() {
+        assert_eq!("4".apply_parse(|it: u8| it + 1), Ok(5));
+        assert!("-4".apply_parse(|it: u8| it + 1).is_err());
+    }
+
+    #[test]
+    fn parse_also() {
+        let mut seen = 0.0;
+        assert_eq!(String::from("1.5").parse_also(|it: &f64| seen = *it), Ok(1.5));
+        assert_eq!(seen, 1.5);
+        assert!("x".parse_also(|_: &f64| unreachable!()).is_err());
+    }
+}
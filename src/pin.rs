@@ -0,0 +1,104 @@
+//! `apply` and `also` variants for pinned values.
+//!
+//! The functions only ever receive a `Pin<&mut T>`, reborrowed from the
+//! pinned pointer, so the pinning guarantees are upheld without any
+//! `unsafe` code: unless `T: Unpin`, the function can not move the value
+//! out, and the value stays pinned after the function returns.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::pin::Pin;
+
+pub trait ApplyPinned<T: ?Sized> {
+    /// Applies the given function to the pinned value and returns the
+    /// result. Consumes the pointer.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyPinned;
+    /// use core::future::Future;
+    /// use core::pin::Pin;
+    /// use core::task::{ Context, Poll, Waker };
+    ///
+    /// # #[cfg(feature = "alloc")] {
+    /// let future = Box::pin(async { 5 });
+    /// let poll = future.apply_pinned(|it| it.poll(&mut Context::from_waker(Waker::noop())));
+    /// assert_eq!(poll, Poll::Ready(5));
+    /// # }
+    /// ```
+    fn apply_pinned<R>(self, function: impl FnOnce(Pin<&mut T>) -> R) -> R;
+
+    /// Applies the given function to the pinned value.
+    /// Returns the pointer, for chaining.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyPinned;
+    /// use core::pin::{ pin, Pin };
+    ///
+    /// let mut x = pin!(1);
+    /// x.as_mut().also_pin(|it| *Pin::into_inner(it) += 1).also_pin(|it| *Pin::into_inner(it) *= 3);
+    /// assert_eq!(*x, 6);
+    /// ```
+    fn also_pin(self, function: impl FnOnce(Pin<&mut T>)) -> Self;
+}
+
+impl<T: ?Sized> ApplyPinned<T> for Pin<&mut T> {
+    #[inline(always)]
+    fn apply_pinned<R>(self, function: impl FnOnce(Pin<&mut T>) -> R) -> R {
+        function(self)
+    }
+
+    #[inline(always)]
+    fn also_pin(mut self, function: impl FnOnce(Pin<&mut T>)) -> Self {
+        function(self.as_mut());
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> ApplyPinned<T> for Pin<Box<T>> {
+    #[inline(always)]
+    fn apply_pinned<R>(mut self, function: impl FnOnce(Pin<&mut T>) -> R) -> R {
+        function(self.as_mut())
+    }
+
+    #[inline(always)]
+    fn also_pin(mut self, function: impl FnOnce(Pin<&mut T>)) -> Self {
+        function(self.as_mut());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::future::Future;
+    use core::marker::PhantomPinned;
+    use core::task::{ Context, Poll, Waker };
+
+    struct Unmovable {
+        value: i32,
+        _pinned: PhantomPinned,
+    }
+
+    fn increment(it: Pin<&mut Unmovable>) {
+        // SAFETY: `value` is not structurally pinned.
+        unsafe { it.get_unchecked_mut().value += 1 };
+    }
+
+    #[test]
+    fn also_pin() {
+        let mut x = core::pin::pin!(Unmovable { value: 1, _pinned: PhantomPinned });
+        x.as_mut().also_pin(increment).also_pin(increment);
+        assert_eq!(x.value, 3);
+    }
+
+    #[test]
+    fn apply_pinned() {
+        let mut context = Context::from_waker(Waker::noop());
+        let future = async { 2 };
+        let future = core::pin::pin!(future);
+        assert_eq!(future.apply_pinned(|it| it.poll(&mut context)), Poll::Ready(2));
+    }
+}
@@ -0,0 +1,88 @@
+//! Operator pipelines, applying functions to a wrapped value with `>>`.
+//!
+//! Only values wrapped in a [`Piped`] support the operator, so no
+//! operator is added to other types.
+//!
+//! ```
+//! use apply_also::piped::piped;
+//!
+//! fn double(x: i32) -> i32 { x * 2 }
+//! fn stringify(x: i32) -> String { x.to_string() }
+//!
+//! let x = piped(21) >> double >> stringify;
+//! assert_eq!(x.into_inner(), "42");
+//! ```
+
+use core::ops::{ Deref, Shr };
+
+/// A value wrapped for operator pipelines, created by [`piped`].
+///
+/// `piped >> f` applies `f` to the value and wraps the result. The
+/// wrapper dereferences to the value. Closures in the middle of a
+/// pipeline need parentheses, as their body would extend over the
+/// rest of the pipeline otherwise.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Piped<T>(T);
+
+/// Wraps the given value for an operator pipeline.
+///
+/// Example:
+/// ```
+/// # use apply_also::piped::piped;
+/// let len = piped("hello") >> str::len >> (|it: usize| it + 1);
+/// assert_eq!(*len, 6);
+/// ```
+#[inline(always)]
+pub fn piped<T>(value: T) -> Piped<T> {
+    Piped(value)
+}
+
+impl<T> Piped<T> {
+    /// Returns the wrapped value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, R, F: FnOnce(T) -> R> Shr<F> for Piped<T> {
+    type Output = Piped<R>;
+
+    #[inline(always)]
+    fn shr(self, function: F) -> Piped<R> {
+        Piped(function(self.0))
+    }
+}
+
+impl<T> Deref for Piped<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for Piped<T> {
+    #[inline(always)]
+    fn from(value: T) -> Self {
+        Piped(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shr() {
+        fn sorted(mut it: Vec<i32>) -> Vec<i32> {
+            it.sort();
+            it
+        }
+
+        let x = piped(vec![3, 1, 2]) >> sorted >> (|it: Vec<i32>| it[0]);
+        assert_eq!(x, piped(1));
+        assert_eq!((Piped::from(2) >> (|it: i32| it.pow(2))).into_inner(), 4);
+    }
+}
@@ -0,0 +1,219 @@
+//! Transformation pipelines that can be assembled at runtime.
+//!
+//! ```
+//! use apply_also::pipeline::Pipeline;
+//!
+//! let trim = true;
+//! let pipeline = Pipeline::new()
+//!     .then(|it: String| it.to_uppercase())
+//!     .then_if(trim, |it| it.trim().to_string())
+//!     .then(|it| it + "!");
+//! assert_eq!(pipeline.run("  hello ".to_string()), "HELLO!");
+//! ```
+
+use crate::ApplyAlso;
+use alloc::{ boxed::Box, format, string::String, vec::Vec };
+use core::fmt::{ self, Debug };
+
+/// A sequence of stages, each transforming a value of type `T`,
+/// which are applied in order by [`Pipeline::run`].
+pub struct Pipeline<T> {
+    stages: Vec<Box<dyn FnOnce(T) -> T>>,
+}
+
+impl<T> Pipeline<T> {
+    /// Creates an empty pipeline, which returns its input unchanged.
+    pub fn new() -> Self {
+        Pipeline { stages: Vec::new() }
+    }
+
+    /// Appends the given stage to this pipeline.
+    pub fn then(mut self, stage: impl FnOnce(T) -> T + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Appends the given stage to this pipeline if the condition holds.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::pipeline::Pipeline;
+    /// let pipeline = Pipeline::new()
+    ///     .then_if(false, |it: i32| it * 2)
+    ///     .then_if(true, |it| it + 1);
+    /// assert_eq!(pipeline.len(), 1);
+    /// assert_eq!(pipeline.run(1), 2);
+    /// ```
+    pub fn then_if(self, condition: bool, stage: impl FnOnce(T) -> T + 'static) -> Self {
+        if condition {
+            self.then(stage)
+        } else {
+            self
+        }
+    }
+
+    /// Appends the given stage to this pipeline in place.
+    pub fn push(&mut self, stage: impl FnOnce(T) -> T + 'static) {
+        self.stages.push(Box::new(stage));
+    }
+
+    /// Returns the number of stages in this pipeline.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns `true` if this pipeline has no stages.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Applies all stages, in order, to the given value and returns
+    /// the result. Consumes the pipeline.
+    pub fn run(self, value: T) -> T {
+        self.stages.into_iter().fold(value, |value, stage| stage(value))
+    }
+}
+
+impl<T> Default for Pipeline<T> {
+    fn default() -> Self {
+        Pipeline::new()
+    }
+}
+
+impl<T> Debug for Pipeline<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline").field("stages", &self.stages.len()).finish()
+    }
+}
+
+/// A value passing through a chain of stages, together with the
+/// `Debug` output of the value after each stage, created by
+/// [`traced`](ApplyTraced::traced).
+///
+/// Its `Display` output lists the stages and values in order.
+///
+/// Example:
+/// ```
+/// # use apply_also::ApplyTraced;
+/// let recorder = "  3, 1, 2 "
+///     .traced("input")
+///     .apply_traced("trim", str::trim)
+///     .apply_traced("split", |it| it.split(", ").collect::<Vec<_>>())
+///     .also_traced("check", |it| assert_eq!(it.len(), 3))
+///     .apply_traced("parse", |it| it.iter().map(|it| it.parse::<i32>().unwrap()).collect::<Vec<_>>());
+/// assert_eq!(recorder.to_string(), r#"input: "  3, 1, 2 "
+/// trim: "3, 1, 2"
+/// split: ["3", "1", "2"]
+/// check: ["3", "1", "2"]
+/// parse: [3, 1, 2]
+/// "#);
+/// assert_eq!(recorder.into_inner(), vec![3, 1, 2]);
+/// ```
+pub struct PipelineRecorder<T> {
+    value: T,
+    stages: Vec<(&'static str, String)>,
+}
+
+impl<T> PipelineRecorder<T> {
+    /// Applies the given function to the value and records the result
+    /// as the given stage.
+    pub fn apply_traced<R: Debug>(self, stage: &'static str, function: impl FnOnce(T) -> R) -> PipelineRecorder<R> {
+        let mut stages = self.stages;
+        let value = function(self.value);
+        stages.push((stage, format!("{:?}", value)));
+        PipelineRecorder { value, stages }
+    }
+
+    /// Applies the given function to a reference of the value and
+    /// records the value as the given stage.
+    pub fn also_traced(mut self, stage: &'static str, function: impl FnOnce(&T)) -> Self where T: Debug {
+        function(&self.value);
+        self.stages.push((stage, format!("{:?}", self.value)));
+        self
+    }
+
+    /// Returns the recorded stages, with the `Debug` output of the
+    /// value after each of them.
+    pub fn stages(&self) -> &[(&'static str, String)] {
+        &self.stages
+    }
+
+    /// Returns the value, discarding the recorded stages.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> fmt::Display for PipelineRecorder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (stage, value) in &self.stages {
+            writeln!(f, "{}: {}", stage, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug> Debug for PipelineRecorder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineRecorder")
+            .field("value", &self.value)
+            .field("stages", &self.stages)
+            .finish()
+    }
+}
+
+pub trait ApplyTraced<T> {
+    /// Starts recording how this value evolves through a chain of
+    /// stages, with this value as the first given stage.
+    fn traced(self, stage: &'static str) -> PipelineRecorder<T> where T: Debug;
+}
+
+impl<T: ApplyAlso> ApplyTraced<T> for T {
+    #[inline(always)]
+    fn traced(self, stage: &'static str) -> PipelineRecorder<T> where T: Debug {
+        PipelineRecorder { stages: Vec::new(), value: self }.also_traced(stage, |_| {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Apply;
+
+    #[test]
+    fn run() {
+        let empty = Pipeline::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.run(3), 3);
+
+        let mut pipeline = Pipeline::new().then(|it: i32| it + 1);
+        pipeline.push(|it| it * 10);
+        assert_eq!(pipeline.len(), 2);
+        assert_eq!(5.apply(|it| pipeline.run(it)), 60);
+    }
+
+    #[test]
+    fn from_config() {
+        let config = ["double", "negate", "unknown", "double"];
+        let pipeline = config.iter().fold(Pipeline::new(), |pipeline, stage| match *stage {
+            "double" => pipeline.then(|it: i64| it * 2),
+            "negate" => pipeline.then(|it| -it),
+            _ => pipeline,
+        });
+        assert_eq!(pipeline.run(3), -12);
+    }
+
+    #[test]
+    fn traced() {
+        let recorder = 2
+            .traced("start")
+            .apply_traced("double", |it| it * 2)
+            .apply_traced("describe", |it| format!("{} items", it));
+        assert_eq!(
+            recorder.stages(),
+            [("start", "2".into()), ("double", "4".into()), ("describe", r#""4 items""#.into())],
+        );
+        assert_eq!(recorder.to_string(), "start: 2\ndouble: 4\ndescribe: \"4 items\"\n");
+        assert_eq!(recorder.into_inner(), "4 items");
+    }
+}
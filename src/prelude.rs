@@ -0,0 +1,97 @@
+//! Re-exports all extension traits, for glob imports.
+//!
+//! ```
+//! use apply_also::prelude::*;
+//!
+//! let x = vec![3, 1, 2].also_mut(|it| it.sort()).apply(|it| it[0]);
+//! assert_eq!(x, 1);
+//! ```
+//!
+//! With the `compat` feature, the prelude also exports `ApplyCompat`,
+//! providing aliases that do not clash with other crates.
+//!
+//! `AsyncReadTeeExt` and `AsyncWriteTeeExt` are not exported: types like
+//! `&[u8]` and `Vec<u8>` implement both the `std::io` and the `futures-io`
+//! traits, so their `tee_read` and `tee_write` would be ambiguous with the
+//! ones of `ReadTeeExt` and `WriteTeeExt`. Import them explicitly.
+//!
+//! With the `tap-compat` feature, the prelude also exports the traits
+//! of the `tap` crate. By default, both crates implement their traits
+//! for all types and their method names do not overlap, so `tap` and `pipe`
+//! can be mixed freely with `also` and `apply` during a migration. The
+//! [`tapped`](crate::tapped) module bridges the other direction.
+//!
+//! ```
+//! # #[cfg(feature = "tap-compat")] {
+//! use apply_also::prelude::*;
+//!
+//! let x = vec![3, 1, 2].tap_mut(|it| it.sort()).also(|it| assert_eq!(it[0], 1)).pipe(|it| it.len());
+//! assert_eq!(x, 3);
+//! # }
+//! ```
+
+pub use crate::{ Also, AlsoRef, Apply, ApplyIf, ApplyRef, Convert, TakeIf, TryAlso, TryApply };
+
+pub use crate::{
+    AlsoAssert, AlsoDyn, ApplyApplier, AlsoOnDrop, Apply2, Apply3, ApplyBoth, ApplyChain, ApplyDyn, ApplyEach, ApplyEnsuring,
+    ApplyInner, ApplyOwned, ApplyPinned, ApplyRepeat, ApplyScoped, ApplyZip, AtomicApplyExt, CellApplyExt,
+    IteratorAlsoExt, OnceApplyExt, OptionApplyExt, ParseApplyExt, RefCellApplyExt, ResultAlsoExt, ResultApplyExt,
+    ThenFn, Update,
+};
+
+#[cfg(feature = "std")]
+pub use crate::{
+    AlsoLimited, AlsoPrint, AlsoRecord, AlsoSend, ApplyCached, ApplyCatch, ApplyRetryWith, ApplyTimed, MutexApplyExt,
+    ReadTeeExt, RwLockApplyExt, WriteTeeExt,
+};
+
+#[cfg(feature = "alloc")]
+pub use crate::{ AlsoMakeMut, ApplyTraced, CowApplyExt, MapAlsoExt };
+
+#[cfg(feature = "async")]
+pub use crate::{ AlsoAsync, ApplyAsync };
+
+#[cfg(feature = "compat")]
+pub use crate::ApplyCompat;
+
+#[cfg(feature = "futures")]
+pub use crate::{ FutureApplyExt, StreamAlsoExt };
+
+#[cfg(feature = "hooks")]
+pub use crate::AlsoHooked;
+
+#[cfg(feature = "log")]
+pub use crate::AlsoLog;
+
+#[cfg(feature = "metrics")]
+pub use crate::AlsoMetrics;
+
+#[cfg(feature = "rayon")]
+pub use crate::ParApplyExt;
+
+#[cfg(feature = "serde")]
+pub use crate::AlsoJson;
+
+#[cfg(feature = "tap-compat")]
+pub use tap::{ Pipe, Tap, TapFallible, TapOptional };
+
+#[cfg(feature = "tokio")]
+pub use crate::AlsoSendAsync;
+
+#[cfg(feature = "tracing")]
+pub use crate::AlsoTrace;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn borrow_methods() {
+        let cell = RefCell::new(vec![1]);
+        cell.also_borrow_mut(|it| it.push(2));
+        assert_eq!(*cell.borrow(), vec![1, 2]);
+        let x = vec![3, 1, 2].also_borrow_mut_as(<[i32]>::sort);
+        assert_eq!(x, vec![1, 2, 3]);
+    }
+}
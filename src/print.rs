@@ -0,0 +1,223 @@
+//! `also` variants printing the value, for tee-style debugging.
+
+use crate::ApplyAlso;
+use core::fmt::{ self, Debug, Display };
+use core::panic::Location;
+use std::io::{ self, Write };
+
+/// Formats bytes like `hexdump -C`: the offset, 16 bytes in hex and
+/// their printable ASCII characters per line, followed by the length.
+struct Hexdump<'a>(&'a [u8]);
+
+impl Display for Hexdump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (line, chunk) in self.0.chunks(16).enumerate() {
+            write!(f, "{:08x}  ", line * 16)?;
+            for i in 0..16 {
+                match chunk.get(i) {
+                    Some(byte) => write!(f, "{:02x} ", byte)?,
+                    None => f.write_str("   ")?,
+                }
+                if i == 7 {
+                    f.write_str(" ")?;
+                }
+            }
+            f.write_str(" |")?;
+            for &byte in chunk {
+                let c = if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' };
+                write!(f, "{}", c)?;
+            }
+            f.write_str("|\n")?;
+        }
+        writeln!(f, "{:08x}", self.0.len())
+    }
+}
+
+pub trait AlsoPrint<T> {
+    /// Prints this value to stderr, together with the source location
+    /// of the call, just like `dbg!`. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, AlsoPrint };
+    /// // prints something like "[src/main.rs:2:32] 512"
+    /// let x = 256.apply(|it| it * 2).also_dbg();
+    /// assert_eq!(x, 512);
+    /// ```
+    fn also_dbg(self) -> T where T: Debug;
+
+    /// Prints the given label and this value to stderr, together with
+    /// the source location of the call. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, AlsoPrint };
+    /// // prints something like "[src/main.rs:2:32] doubled = 512"
+    /// let x = 256.apply(|it| it * 2).also_dbg_with("doubled");
+    /// assert_eq!(x, 512);
+    /// ```
+    fn also_dbg_with(self, label: &str) -> T where T: Debug;
+
+    /// Prints the `Display` representation of this value to stderr,
+    /// without a source location. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, AlsoPrint };
+    /// // prints "512"
+    /// let x = 256.apply(|it| it * 2).also_display();
+    /// assert_eq!(x, 512);
+    /// ```
+    fn also_display(self) -> T where T: Display;
+
+    /// Prints the pretty-printed `Debug` representation of this value
+    /// to stderr. Unlike [`also_dbg`](AlsoPrint::also_dbg), the source
+    /// location of the call is omitted, so only the value is printed.
+    /// Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoPrint;
+    /// // prints the vector, one element per line
+    /// let x = vec![1, 2].also_dbg_pretty();
+    /// assert_eq!(x, vec![1, 2]);
+    /// ```
+    fn also_dbg_pretty(self) -> T where T: Debug;
+
+    /// Prints a hex dump of the bytes of this value to stderr, in the
+    /// format of `hexdump -C`, after the source location of the call.
+    /// Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoPrint;
+    /// // prints something like
+    /// // [src/main.rs:3:31]
+    /// // 00000000  68 65 6c 6c 6f 0a                                 |hello.|
+    /// // 00000006
+    /// let x = b"hello\n".to_vec().also_hexdump();
+    /// assert_eq!(x, b"hello\n");
+    /// ```
+    fn also_hexdump(self) -> T where T: AsRef<[u8]>;
+
+    /// Writes the `Display` representation of this value, followed by
+    /// a newline, to the given writer. Returns the original value, or
+    /// the error if writing failed.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoPrint;
+    /// let mut out = Vec::new();
+    /// let x = 512.also_write_to(&mut out)?;
+    /// assert_eq!(x, 512);
+    /// assert_eq!(out, b"512\n");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    fn also_write_to(self, writer: &mut impl Write) -> io::Result<T> where T: Display;
+
+    /// Writes the `Debug` representation of this value, followed by
+    /// a newline, to the given writer, like
+    /// [`also_write_to`](AlsoPrint::also_write_to).
+    /// Returns the original value, or the error if writing failed.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoPrint;
+    /// let mut out = Vec::new();
+    /// let x = "a".also_write_dbg_to(&mut out)?;
+    /// assert_eq!(x, "a");
+    /// assert_eq!(out, b"\"a\"\n");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    fn also_write_dbg_to(self, writer: &mut impl Write) -> io::Result<T> where T: Debug;
+}
+
+impl<T: ApplyAlso> AlsoPrint<T> for T {
+    #[inline(always)]
+    #[track_caller]
+    fn also_dbg(self) -> T where T: Debug {
+        let location = Location::caller();
+        eprintln!("[{}:{}:{}] {:#?}", location.file(), location.line(), location.column(), self);
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_dbg_with(self, label: &str) -> T where T: Debug {
+        let location = Location::caller();
+        eprintln!("[{}:{}:{}] {} = {:#?}", location.file(), location.line(), location.column(), label, self);
+        self
+    }
+
+    #[inline(always)]
+    fn also_display(self) -> T where T: Display {
+        eprintln!("{}", self);
+        self
+    }
+
+    #[inline(always)]
+    fn also_dbg_pretty(self) -> T where T: Debug {
+        eprintln!("{:#?}", self);
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_hexdump(self) -> T where T: AsRef<[u8]> {
+        let location = Location::caller();
+        eprint!("[{}:{}:{}]\n{}", location.file(), location.line(), location.column(), Hexdump(self.as_ref()));
+        self
+    }
+
+    #[inline(always)]
+    fn also_write_to(self, writer: &mut impl Write) -> io::Result<T> where T: Display {
+        writeln!(writer, "{}", self)?;
+        Ok(self)
+    }
+
+    #[inline(always)]
+    fn also_write_dbg_to(self, writer: &mut impl Write) -> io::Result<T> where T: Debug {
+        writeln!(writer, "{:?}", self)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn also_dbg() {
+        assert_eq!(vec![1, 2].also_dbg(), vec![1, 2]);
+        assert_eq!("x".also_dbg_with("label"), "x");
+    }
+
+    #[test]
+    fn also_display() {
+        assert_eq!(5.also_display(), 5);
+        assert_eq!(Some("a").also_dbg_pretty(), Some("a"));
+    }
+
+    #[test]
+    fn also_hexdump() {
+        assert_eq!(
+            Hexdump(b"hello world\n\x00 0123456789").to_string(),
+            "00000000  68 65 6c 6c 6f 20 77 6f  72 6c 64 0a 00 20 30 31  |hello world.. 01|\n\
+             00000010  32 33 34 35 36 37 38 39                           |23456789|\n\
+             00000018\n",
+        );
+        assert_eq!(Hexdump(b"").to_string(), "00000000\n");
+        assert_eq!("abc".also_hexdump(), "abc");
+    }
+
+    #[test]
+    fn also_write_to() {
+        let mut out = Vec::new();
+        let x = 1.5.also_write_to(&mut out).and_then(|it| it.also_write_dbg_to(&mut out)).unwrap();
+        assert_eq!(x, 1.5);
+        assert_eq!(out, b"1.5\n1.5\n");
+
+        let mut full = [0u8; 2];
+        assert!("abc".also_write_to(&mut &mut full[..]).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+//! `also` variant for the reference counted pointers `Rc` and `Arc`.
+
+use alloc::rc::Rc;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+pub trait AlsoMakeMut<T> {
+    /// Applies the given function to a mutable reference of the
+    /// pointed-to value, cloning the value first if it is shared
+    /// (clone-on-write, see `Rc::make_mut`). Returns the pointer.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoMakeMut;
+    /// use std::sync::Arc;
+    ///
+    /// let shared = Arc::new(vec![1, 2]);
+    /// let changed = Arc::clone(&shared).also_make_mut(|it| it.push(3));
+    /// assert_eq!(*shared, vec![1, 2]);
+    /// assert_eq!(*changed, vec![1, 2, 3]);
+    /// ```
+    fn also_make_mut(self, function: impl FnOnce(&mut T)) -> Self where T: Clone;
+}
+
+impl<T> AlsoMakeMut<T> for Rc<T> {
+    #[inline(always)]
+    fn also_make_mut(mut self, function: impl FnOnce(&mut T)) -> Self where T: Clone {
+        function(Rc::make_mut(&mut self));
+        self
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> AlsoMakeMut<T> for Arc<T> {
+    #[inline(always)]
+    fn also_make_mut(mut self, function: impl FnOnce(&mut T)) -> Self where T: Clone {
+        function(Arc::make_mut(&mut self));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn also_make_mut() {
+        let unique = Rc::new(1);
+        let pointer = Rc::as_ptr(&unique);
+        let unique = unique.also_make_mut(|it| *it += 1);
+        assert_eq!(*unique, 2);
+        assert_eq!(Rc::as_ptr(&unique), pointer);
+
+        let shared = Arc::new(String::from("a"));
+        let changed = Arc::clone(&shared).also_make_mut(|it| it.push('b'));
+        assert_eq!((shared.as_str(), changed.as_str()), ("a", "ab"));
+    }
+}
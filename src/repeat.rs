@@ -0,0 +1,255 @@
+//! `apply` and `also` variants applying a function repeatedly.
+
+use crate::ApplyAlso;
+use core::ops::ControlFlow;
+
+pub trait ApplyRepeat<T> {
+    /// Applies the given function to this value, then to the result,
+    /// and so on, until the result equals its input. Returns that
+    /// fixed point. Consumes the value.
+    ///
+    /// This loops forever if the function never reaches a fixed point,
+    /// see [`apply_until_stable_at_most`](ApplyRepeat::apply_until_stable_at_most)
+    /// for a safety cap.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRepeat;
+    /// let path = "/usr//local///bin".to_string().apply_until_stable(|it| it.replace("//", "/"));
+    /// assert_eq!(path, "/usr/local/bin");
+    /// ```
+    fn apply_until_stable(self, function: impl FnMut(T) -> T) -> T where T: PartialEq + Clone;
+
+    /// Like [`apply_until_stable`](ApplyRepeat::apply_until_stable), but
+    /// applies the function at most `max_iterations` times. Returns the
+    /// last result, even if it is not a fixed point yet.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRepeat;
+    /// let x = 1.apply_until_stable_at_most(10, |it| it * 2);
+    /// assert_eq!(x, 1024);
+    /// ```
+    fn apply_until_stable_at_most(self, max_iterations: usize, function: impl FnMut(T) -> T) -> T where T: PartialEq + Clone;
+
+    /// Applies the given function to this value, then to the result,
+    /// and so on, `n` times in total. Returns the last result, or the
+    /// value itself if `n` is zero. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRepeat;
+    /// let x = 1u64.apply_n(10, |it| it * 2);
+    /// assert_eq!(x, 1024);
+    /// ```
+    fn apply_n(self, n: usize, function: impl FnMut(T) -> T) -> T;
+
+    /// Like [`apply_n`](ApplyRepeat::apply_n), but with the count known
+    /// at compile time, so that the loop can be unrolled.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRepeat;
+    /// let x = 1u64.apply_times::<10>(|it| it * 2);
+    /// assert_eq!(x, 1024);
+    /// ```
+    fn apply_times<const N: usize>(self, function: impl FnMut(T) -> T) -> T;
+
+    /// Applies the given function to this value, then to the result,
+    /// and so on, as long as the predicate holds for the current value.
+    /// Returns the first value the predicate does not hold for.
+    /// Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRepeat;
+    /// let x = 7.apply_while(|it| it % 2 == 1, |it| it * 3 + 1);
+    /// assert_eq!(x, 22);
+    /// ```
+    fn apply_while(self, predicate: impl FnMut(&T) -> bool, function: impl FnMut(T) -> T) -> T;
+
+    /// Applies the given function to a mutable reference of this value
+    /// over and over, until it breaks. Returns the value together with
+    /// the payload of the break, which is always `Some`, as in the
+    /// result of [`also_loop_at_most`](ApplyRepeat::also_loop_at_most).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRepeat;
+    /// use core::ops::ControlFlow;
+    ///
+    /// let (fib, steps) = (0, 1, 0).also_loop(|(a, b, steps)| {
+    ///     if *b > 50 {
+    ///         return ControlFlow::Break(*steps);
+    ///     }
+    ///     *steps += 1;
+    ///     (*a, *b) = (*b, *a + *b);
+    ///     ControlFlow::Continue(())
+    /// });
+    /// assert_eq!((fib.1, steps), (55, Some(9)));
+    /// ```
+    fn also_loop<B>(self, function: impl FnMut(&mut T) -> ControlFlow<B>) -> (T, Option<B>);
+
+    /// Like [`also_loop`](ApplyRepeat::also_loop), but applies the
+    /// function at most `max` times. Returns the value together with
+    /// the payload of the break, or `None` if the function did not
+    /// break within `max` calls.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRepeat;
+    /// use core::ops::ControlFlow;
+    ///
+    /// let collatz = |it: &mut u64| {
+    ///     if *it == 1 {
+    ///         return ControlFlow::Break(());
+    ///     }
+    ///     *it = if *it % 2 == 0 { *it / 2 } else { *it * 3 + 1 };
+    ///     ControlFlow::Continue(())
+    /// };
+    /// assert_eq!(6.also_loop_at_most(10, collatz), (1, Some(())));
+    /// assert_eq!(27.also_loop_at_most(10, collatz), (214, None));
+    /// ```
+    fn also_loop_at_most<B>(self, max: usize, function: impl FnMut(&mut T) -> ControlFlow<B>) -> (T, Option<B>);
+}
+
+impl<T: ApplyAlso> ApplyRepeat<T> for T {
+    #[inline(always)]
+    fn apply_until_stable(self, mut function: impl FnMut(T) -> T) -> T where T: PartialEq + Clone {
+        let mut value = self;
+        loop {
+            let next = function(value.clone());
+            if next == value {
+                return next;
+            }
+            value = next;
+        }
+    }
+
+    #[inline(always)]
+    fn apply_until_stable_at_most(self, max_iterations: usize, mut function: impl FnMut(T) -> T) -> T where T: PartialEq + Clone {
+        let mut value = self;
+        for _ in 0..max_iterations {
+            let next = function(value.clone());
+            if next == value {
+                return next;
+            }
+            value = next;
+        }
+        value
+    }
+
+    #[inline(always)]
+    fn apply_n(self, n: usize, mut function: impl FnMut(T) -> T) -> T {
+        let mut value = self;
+        for _ in 0..n {
+            value = function(value);
+        }
+        value
+    }
+
+    #[inline(always)]
+    fn apply_times<const N: usize>(self, function: impl FnMut(T) -> T) -> T {
+        self.apply_n(N, function)
+    }
+
+    #[inline(always)]
+    fn apply_while(self, mut predicate: impl FnMut(&T) -> bool, mut function: impl FnMut(T) -> T) -> T {
+        let mut value = self;
+        while predicate(&value) {
+            value = function(value);
+        }
+        value
+    }
+
+    #[inline(always)]
+    fn also_loop<B>(mut self, mut function: impl FnMut(&mut T) -> ControlFlow<B>) -> (T, Option<B>) {
+        loop {
+            if let ControlFlow::Break(payload) = function(&mut self) {
+                return (self, Some(payload));
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn also_loop_at_most<B>(mut self, max: usize, mut function: impl FnMut(&mut T) -> ControlFlow<B>) -> (T, Option<B>) {
+        for _ in 0..max {
+            if let ControlFlow::Break(payload) = function(&mut self) {
+                return (self, Some(payload));
+            }
+        }
+        (self, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_until_stable() {
+        let mut calls = 0;
+        let x = 100.apply_until_stable(|it| {
+            calls += 1;
+            (it / 2).max(3)
+        });
+        assert_eq!(x, 3);
+        assert_eq!(calls, 6);
+        assert_eq!(5.apply_until_stable(|it| it), 5);
+    }
+
+    #[test]
+    fn apply_until_stable_at_most() {
+        assert_eq!(100.apply_until_stable_at_most(2, |it| (it / 2).max(3)), 25);
+        assert_eq!(100.apply_until_stable_at_most(100, |it| (it / 2).max(3)), 3);
+        assert_eq!(7.apply_until_stable_at_most(0, |it| it + 1), 7);
+    }
+
+    #[test]
+    fn apply_n() {
+        let mut steps = Vec::new();
+        let x = 3.apply_n(3, |it| {
+            steps.push(it);
+            it + 1
+        });
+        assert_eq!(x, 6);
+        assert_eq!(steps, vec![3, 4, 5]);
+        assert_eq!("a".apply_n(0, |_| "b"), "a");
+    }
+
+    #[test]
+    fn apply_times() {
+        assert_eq!(3.apply_times::<4>(|it| it * 3), 243);
+        assert_eq!("a".apply_times::<0>(|_| "b"), "a");
+    }
+
+    #[test]
+    fn apply_while() {
+        assert_eq!(1.apply_while(|it| *it < 100, |it| it * 3), 243);
+        assert_eq!(100.apply_while(|it| *it < 100, |_| unreachable!()), 100);
+    }
+
+    #[test]
+    fn also_loop() {
+        let (x, found) = vec![3, 1, 4, 1, 5].also_loop(|it| match it.pop() {
+            Some(n) if n % 2 == 0 => ControlFlow::Break(n),
+            _ => ControlFlow::Continue(()),
+        });
+        assert_eq!(x, vec![3, 1]);
+        assert_eq!(found, Some(4));
+    }
+
+    #[test]
+    fn also_loop_at_most() {
+        let mut calls = 0;
+        let countdown = |it: &mut i32| {
+            calls += 1;
+            *it -= 1;
+            if *it == 0 { ControlFlow::Break("done") } else { ControlFlow::Continue(()) }
+        };
+        assert_eq!(3.also_loop_at_most(3, countdown), (0, Some("done")));
+        assert_eq!(calls, 3);
+        assert_eq!(5.also_loop_at_most(2, |it| { *it -= 1; ControlFlow::<()>::Continue(()) }), (3, None));
+        assert_eq!(5.also_loop_at_most(0, |_| ControlFlow::Break(())), (5, None));
+    }
+}
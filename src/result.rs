@@ -0,0 +1,209 @@
+//! `apply` and `also` variants for `Result`.
+
+pub trait ResultAlsoExt<T, E> {
+    /// Applies the given function to a reference of the contained
+    /// value, if this is `Ok`. Returns the original result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ResultAlsoExt;
+    /// let x: Result<i32, String> = Ok(3).also_ok(|it| {
+    ///     println!("success: {}", it);
+    /// });
+    /// assert_eq!(x, Ok(3));
+    /// ```
+    fn also_ok(self, function: impl FnOnce(&T)) -> Result<T, E>;
+
+    /// Applies the given function to a reference of the contained
+    /// error, if this is `Err`. Returns the original result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ResultAlsoExt;
+    /// let x = "abc".parse::<i32>().also_err(|e| {
+    ///     eprintln!("failed to parse: {}", e);
+    /// });
+    /// assert!(x.is_err());
+    /// ```
+    fn also_err(self, function: impl FnOnce(&E)) -> Result<T, E>;
+
+    /// Applies the first function to a reference of the contained
+    /// value if this is `Ok`, and the second function to a reference
+    /// of the contained error if this is `Err`. Returns the original
+    /// result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ResultAlsoExt;
+    /// let x = "256".parse::<i32>().also_both(
+    ///     |it| println!("parsed {}", it),
+    ///     |e| eprintln!("failed to parse: {}", e),
+    /// );
+    /// assert_eq!(x, Ok(256));
+    /// ```
+    fn also_both(self, on_ok: impl FnOnce(&T), on_err: impl FnOnce(&E)) -> Result<T, E>;
+}
+
+pub trait ResultApplyExt<T, E> {
+    /// Applies the given fallible function to the contained value,
+    /// if this is `Ok`, and returns the result. Otherwise, returns
+    /// the original error. Equivalent to `Result::and_then`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ResultApplyExt;
+    /// let x = "256".parse::<i32>().and_apply(|it| (it * 2).to_string().parse::<i32>());
+    /// assert_eq!(x, Ok(512));
+    /// ```
+    fn and_apply<R>(self, function: impl FnOnce(T) -> Result<R, E>) -> Result<R, E>;
+
+    /// Applies the given fallible function to a reference of the
+    /// contained value, if this is `Ok`, and returns the result.
+    /// Otherwise, returns the original error.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ResultApplyExt;
+    /// let x = Ok::<_, std::num::ParseIntError>(String::from("12"))
+    ///     .and_apply_ref(|it| it.parse::<i32>());
+    /// assert_eq!(x, Ok(12));
+    /// ```
+    fn and_apply_ref<R>(self, function: impl FnOnce(&T) -> Result<R, E>) -> Result<R, E>;
+
+    /// Applies the given fallible function to a reference of the
+    /// contained value, if this is `Ok`. Returns the original result,
+    /// or the error returned by the function.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ResultApplyExt;
+    /// let x = "-3".parse::<i32>()
+    ///     .map_err(|e| e.to_string())
+    ///     .and_also(|it| if *it >= 0 { Ok(()) } else { Err("negative".into()) });
+    /// assert_eq!(x, Err("negative".to_string()));
+    /// ```
+    fn and_also(self, function: impl FnOnce(&T) -> Result<(), E>) -> Result<T, E>;
+
+    /// Applies the given fallible function to a mutable reference of
+    /// the contained value, if this is `Ok`. Returns the mutated result,
+    /// or the error returned by the function.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ResultApplyExt;
+    /// # use std::num::ParseIntError;
+    /// let x = Ok::<_, ParseIntError>(vec![1]).and_also_mut(|it| {
+    ///     it.push("2".parse::<i32>()?);
+    ///     Ok(())
+    /// });
+    /// assert_eq!(x, Ok(vec![1, 2]));
+    /// ```
+    fn and_also_mut(self, function: impl FnOnce(&mut T) -> Result<(), E>) -> Result<T, E>;
+}
+
+impl<T, E> ResultAlsoExt<T, E> for Result<T, E> {
+    #[inline(always)]
+    fn also_ok(self, function: impl FnOnce(&T)) -> Result<T, E> {
+        if let Ok(value) = &self {
+            function(value);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_err(self, function: impl FnOnce(&E)) -> Result<T, E> {
+        if let Err(error) = &self {
+            function(error);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn also_both(self, on_ok: impl FnOnce(&T), on_err: impl FnOnce(&E)) -> Result<T, E> {
+        match &self {
+            Ok(value) => on_ok(value),
+            Err(error) => on_err(error),
+        }
+        self
+    }
+}
+
+impl<T, E> ResultApplyExt<T, E> for Result<T, E> {
+    #[inline(always)]
+    fn and_apply<R>(self, function: impl FnOnce(T) -> Result<R, E>) -> Result<R, E> {
+        self.and_then(function)
+    }
+
+    #[inline(always)]
+    fn and_apply_ref<R>(self, function: impl FnOnce(&T) -> Result<R, E>) -> Result<R, E> {
+        self.and_then(|value| function(&value))
+    }
+
+    #[inline(always)]
+    fn and_also(self, function: impl FnOnce(&T) -> Result<(), E>) -> Result<T, E> {
+        let value = self?;
+        function(&value)?;
+        Ok(value)
+    }
+
+    #[inline(always)]
+    fn and_also_mut(self, function: impl FnOnce(&mut T) -> Result<(), E>) -> Result<T, E> {
+        let mut value = self?;
+        function(&mut value)?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn also_ok() {
+        let mut seen = Vec::new();
+        let x: Result<_, i32> = Ok(1)
+            .also_ok(|it| seen.push(*it))
+            .also_err(|e| seen.push(*e));
+        assert_eq!(x, Ok(1));
+
+        let y: Result<i32, _> = Err(2)
+            .also_ok(|it| seen.push(*it))
+            .also_err(|e| seen.push(*e));
+        assert_eq!(y, Err(2));
+        assert_eq!(seen, vec![1, 2]);
+    }
+
+    #[test]
+    fn also_both() {
+        let (mut ok, mut err) = (0, 0);
+        let x = Ok::<_, i32>(1).also_both(|it| ok = *it, |e| err = *e);
+        assert_eq!((x, ok, err), (Ok(1), 1, 0));
+
+        let y = Err::<i32, _>(2).also_both(|it| ok = *it, |e| err = *e);
+        assert_eq!((y, ok, err), (Err(2), 1, 2));
+    }
+
+    #[test]
+    fn and_apply() {
+        let x: Result<i32, &str> = Ok(2).and_apply(|it| Ok(it + 1));
+        assert_eq!(x, Ok(3));
+
+        let y: Result<i32, &str> = Err("error").and_apply(|it: i32| Ok(it + 1));
+        assert_eq!(y, Err("error"));
+
+        let z: Result<usize, &str> = Ok(vec![1, 2]).and_apply_ref(|it| Ok(it.len()));
+        assert_eq!(z, Ok(2));
+    }
+
+    #[test]
+    fn and_also() {
+        let x: Result<_, &str> = Ok(2).and_also(|_| Err("invalid"));
+        assert_eq!(x, Err("invalid"));
+
+        let y: Result<_, &str> = Ok(vec![2]).and_also_mut(|it| {
+            it.push(3);
+            Ok(())
+        });
+        assert_eq!(y, Ok(vec![2, 3]));
+    }
+}
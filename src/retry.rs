@@ -0,0 +1,233 @@
+//! Retrying fallible functions with a delay between the attempts.
+//!
+//! ```
+//! use apply_also::ApplyRetryWith;
+//! use apply_also::retry::RetryPolicy;
+//! use std::time::Duration;
+//!
+//! let policy = RetryPolicy::exponential(4, Duration::from_millis(1)).with_jitter();
+//! let mut calls = 0;
+//! let x = "localhost".apply_retry_with(policy, |host| {
+//!     calls += 1;
+//!     if calls < 3 { Err("connection refused") } else { Ok(format!("connected to {}", host)) }
+//! });
+//! assert_eq!(x.as_deref(), Ok("connected to localhost"));
+//! ```
+
+use crate::ApplyAlso;
+#[cfg(feature = "async")]
+use core::future::Future;
+use std::collections::hash_map::RandomState;
+use std::hash::{ BuildHasher, Hasher };
+use std::thread;
+use std::time::Duration;
+
+/// How often a function is attempted, and how long to wait in between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    attempts: usize,
+    delay: Duration,
+    factor: u32,
+    max_delay: Duration,
+    jitter: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a policy attempting the function at most `attempts`
+    /// times, but at least once, waiting `delay` between the attempts.
+    pub fn fixed(attempts: usize, delay: Duration) -> Self {
+        RetryPolicy { attempts, delay, factor: 1, max_delay: Duration::MAX, jitter: false }
+    }
+
+    /// Creates a policy attempting the function at most `attempts`
+    /// times, but at least once, waiting `initial` after the first
+    /// attempt and doubling the delay after each further attempt.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::retry::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::exponential(5, Duration::from_millis(100))
+    ///     .with_max_delay(Duration::from_millis(300));
+    /// assert_eq!(policy.delay(0), Duration::from_millis(100));
+    /// assert_eq!(policy.delay(1), Duration::from_millis(200));
+    /// assert_eq!(policy.delay(2), Duration::from_millis(300));
+    /// ```
+    pub fn exponential(attempts: usize, initial: Duration) -> Self {
+        RetryPolicy { factor: 2, ..RetryPolicy::fixed(attempts, initial) }
+    }
+
+    /// Limits the delay between two attempts to the given maximum.
+    pub fn with_max_delay(self, max_delay: Duration) -> Self {
+        RetryPolicy { max_delay, ..self }
+    }
+
+    /// Randomizes each delay to between half of and the full delay,
+    /// so that clients retrying at the same time spread out.
+    pub fn with_jitter(self) -> Self {
+        RetryPolicy { jitter: true, ..self }
+    }
+
+    /// Returns the maximum number of attempts.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Returns the delay after the given failed attempt, counting from
+    /// zero, before jitter is applied.
+    pub fn delay(&self, attempt: usize) -> Duration {
+        let exponent = attempt.min(u32::MAX as usize) as u32;
+        self.factor.checked_pow(exponent)
+            .and_then(|factor| self.delay.checked_mul(factor))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+
+    /// Returns the delay after the given failed attempt, with jitter
+    /// applied if enabled.
+    fn jittered_delay(&self, attempt: usize) -> Duration {
+        let delay = self.delay(attempt);
+        if !self.jitter {
+            return delay;
+        }
+        // `RandomState` is randomly seeded, which is good enough here
+        let random = RandomState::new().build_hasher().finish();
+        delay / 2 + (delay / 2).mul_f64(random as f64 / u64::MAX as f64)
+    }
+}
+
+pub trait ApplyRetryWith<T> {
+    /// Applies the given fallible function to a reference of this
+    /// value until it succeeds, as often as the given policy allows,
+    /// blocking the thread for the policy's delay between attempts.
+    /// Returns the first success, or the last error. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRetryWith;
+    /// # use apply_also::retry::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let x = "x".apply_retry_with(RetryPolicy::fixed(2, Duration::from_millis(1)), |it| it.parse::<i32>());
+    /// assert!(x.is_err());
+    /// ```
+    fn apply_retry_with<R, E>(self, policy: RetryPolicy, function: impl FnMut(&T) -> Result<R, E>) -> Result<R, E>;
+
+    /// Applies the given fallible async function to a reference of
+    /// this value until it succeeds, as often as the given policy
+    /// allows, awaiting the future returned by `sleep` for the policy's
+    /// delay between attempts. Returns the first success, or the last
+    /// error, once the function resolves. Consumes the value.
+    ///
+    /// `sleep` is usually the sleep function of the async runtime.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyRetryWith;
+    /// # use apply_also::retry::RetryPolicy;
+    /// # use std::time::Duration;
+    /// # async fn sleep(_: Duration) {}
+    /// # async fn connect(_: &str) -> Result<(), ()> { Ok(()) }
+    /// # async fn run() {
+    /// let policy = RetryPolicy::exponential(3, Duration::from_millis(50));
+    /// let connection = "localhost"
+    ///     .apply_retry_with_async(policy, sleep, async |host| connect(host).await)
+    ///     .await;
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    fn apply_retry_with_async<R, E, S: Future<Output = ()>>(
+        self,
+        policy: RetryPolicy,
+        sleep: impl FnMut(Duration) -> S,
+        function: impl AsyncFnMut(&T) -> Result<R, E>,
+    ) -> impl Future<Output = Result<R, E>>;
+}
+
+impl<T: ApplyAlso> ApplyRetryWith<T> for T {
+    fn apply_retry_with<R, E>(self, policy: RetryPolicy, mut function: impl FnMut(&T) -> Result<R, E>) -> Result<R, E> {
+        let mut result = function(&self);
+        for attempt in 1..policy.attempts {
+            if result.is_ok() {
+                break;
+            }
+            thread::sleep(policy.jittered_delay(attempt - 1));
+            result = function(&self);
+        }
+        result
+    }
+
+    #[cfg(feature = "async")]
+    async fn apply_retry_with_async<R, E, S: Future<Output = ()>>(
+        self,
+        policy: RetryPolicy,
+        mut sleep: impl FnMut(Duration) -> S,
+        mut function: impl AsyncFnMut(&T) -> Result<R, E>,
+    ) -> Result<R, E> {
+        let mut result = function(&self).await;
+        for attempt in 1..policy.attempts {
+            if result.is_ok() {
+                break;
+            }
+            sleep(policy.jittered_delay(attempt - 1)).await;
+            result = function(&self).await;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn delay() {
+        let fixed = RetryPolicy::fixed(3, Duration::from_millis(10));
+        assert_eq!(fixed.delay(0), fixed.delay(5));
+
+        let exponential = RetryPolicy::exponential(3, Duration::from_secs(1));
+        assert_eq!(exponential.delay(3), Duration::from_secs(8));
+        assert_eq!(exponential.delay(100), Duration::MAX);
+
+        let jittered = exponential.with_max_delay(Duration::from_secs(4)).with_jitter();
+        for attempt in 0..10 {
+            let delay = jittered.jittered_delay(attempt);
+            assert!(delay >= jittered.delay(attempt) / 2 && delay <= jittered.delay(attempt));
+        }
+    }
+
+    #[test]
+    fn apply_retry_with() {
+        let mut calls = 0;
+        let start = Instant::now();
+        let x: Result<(), _> = 1.apply_retry_with(RetryPolicy::exponential(3, Duration::from_millis(5)), |_| {
+            calls += 1;
+            Err(calls)
+        });
+        assert_eq!(x, Err(3));
+        assert!(start.elapsed() >= Duration::from_millis(15));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn apply_retry_with_async() {
+        use crate::test_util::block_on;
+
+        let mut slept = Vec::new();
+        let mut calls = 0;
+        let future = 2.apply_retry_with_async(
+            RetryPolicy::exponential(4, Duration::from_millis(10)),
+            |it| {
+                slept.push(it);
+                async {}
+            },
+            async |it| {
+                calls += 1;
+                if calls < 3 { Err(()) } else { Ok(it * 2) }
+            },
+        );
+        assert_eq!(block_on(future), Ok(4));
+        assert_eq!(slept, vec![Duration::from_millis(10), Duration::from_millis(20)]);
+    }
+}
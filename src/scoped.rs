@@ -0,0 +1,71 @@
+//! `apply` variants whose functions can return borrows of the value.
+//!
+//! [`Apply::apply_ref`](crate::Apply::apply_ref) consumes the value, so
+//! its function can not return anything borrowed from it. These
+//! variants keep the value alive for as long as the borrow is used:
+//! either by borrowing it from the caller, or by passing the borrow on
+//! to a second function before the value is dropped.
+
+use crate::ApplyAlso;
+
+pub trait ApplyScoped<T: ?Sized> {
+    /// Applies the given function to a reference of this value and
+    /// returns the result, which may borrow from the value.
+    /// Does not consume the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyScoped;
+    /// let line = String::from("key = value");
+    /// let (key, value) = line.with_ref(|it| it.split_once(" = ").unwrap());
+    /// assert_eq!((key, value), ("key", "value"));
+    /// ```
+    fn with_ref<'a, R>(&'a self, function: impl FnOnce(&'a T) -> R) -> R where T: 'a;
+
+    /// Applies the given projection to a reference of this value, then
+    /// applies the given scope function to the borrowed result and
+    /// returns its result. The value is kept alive until the scope
+    /// function returns, then dropped.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyScoped;
+    /// let words = String::from("  apply also  ")
+    ///     .apply_ref_scoped(|it| it.trim(), |it| it.split(' ').map(str::to_uppercase).collect::<Vec<_>>());
+    /// assert_eq!(words, ["APPLY", "ALSO"]);
+    /// ```
+    fn apply_ref_scoped<U: ?Sized, R>(self, project: impl FnOnce(&T) -> &U, scope: impl FnOnce(&U) -> R) -> R where T: Sized;
+}
+
+impl<T: ApplyAlso + ?Sized> ApplyScoped<T> for T {
+    #[inline(always)]
+    fn with_ref<'a, R>(&'a self, function: impl FnOnce(&'a T) -> R) -> R where T: 'a {
+        function(self)
+    }
+
+    #[inline(always)]
+    fn apply_ref_scoped<U: ?Sized, R>(self, project: impl FnOnce(&T) -> &U, scope: impl FnOnce(&U) -> R) -> R where T: Sized {
+        scope(project(&self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_ref() {
+        let text = "a,b,c".to_string();
+        let fields: Vec<&str> = text.with_ref(|it| it.split(',').collect());
+        assert_eq!(fields, ["a", "b", "c"]);
+        assert_eq!("hello".with_ref(|it| &it[1..]), "ello");
+    }
+
+    #[test]
+    fn apply_ref_scoped() {
+        let len = vec![1, 2, 3, 4].apply_ref_scoped(|it| &it[1..3], <[i32]>::len);
+        assert_eq!(len, 2);
+        let first = "x y".to_string().apply_ref_scoped(|it| it.as_str(), |it| it.split(' ').next().map(str::to_string));
+        assert_eq!(first.as_deref(), Some("x"));
+    }
+}
@@ -0,0 +1,141 @@
+//! `also`-style adapters for streams.
+
+use futures_core::Stream;
+use core::pin::Pin;
+use core::task::{ Context, Poll };
+
+pub trait StreamAlsoExt: Stream + Sized {
+    /// Applies the given function to a reference of every item
+    /// of this stream. The items are passed on unchanged.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::StreamAlsoExt;
+    /// # use futures_core::Stream;
+    /// # fn run(stream: impl Stream<Item = u32>) {
+    /// let logged = stream.also_each(|it| {
+    ///     println!("received {}", it);
+    /// });
+    /// # }
+    /// ```
+    fn also_each<F: FnMut(&Self::Item)>(self, function: F) -> AlsoEach<Self, F>;
+
+    /// Applies the given function to a mutable reference of every
+    /// item of this stream. The mutated items are passed on.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::StreamAlsoExt;
+    /// # use futures_core::Stream;
+    /// # fn run(stream: impl Stream<Item = Vec<u8>>) {
+    /// let terminated = stream.also_each_mut(|it| {
+    ///     it.push(b'\n');
+    /// });
+    /// # }
+    /// ```
+    fn also_each_mut<F: FnMut(&mut Self::Item)>(self, function: F) -> AlsoEachMut<Self, F>;
+}
+
+/// Stream returned by [`StreamAlsoExt::also_each`].
+#[must_use = "streams do nothing unless polled"]
+pub struct AlsoEach<S, F> {
+    stream: S,
+    function: F,
+}
+
+/// Stream returned by [`StreamAlsoExt::also_each_mut`].
+#[must_use = "streams do nothing unless polled"]
+pub struct AlsoEachMut<S, F> {
+    stream: S,
+    function: F,
+}
+
+impl<S: Stream> StreamAlsoExt for S {
+    fn also_each<F: FnMut(&Self::Item)>(self, function: F) -> AlsoEach<Self, F> {
+        AlsoEach { stream: self, function }
+    }
+
+    fn also_each_mut<F: FnMut(&mut Self::Item)>(self, function: F) -> AlsoEachMut<Self, F> {
+        AlsoEachMut { stream: self, function }
+    }
+}
+
+impl<S: Stream, F: FnMut(&S::Item)> Stream for AlsoEach<S, F> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        // SAFETY: `stream` is structurally pinned and never moved out of,
+        // `function` is not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        let item = stream.poll_next(cx);
+        if let Poll::Ready(Some(item)) = &item {
+            (this.function)(item);
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S: Stream, F: FnMut(&mut S::Item)> Stream for AlsoEachMut<S, F> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        // SAFETY: see `AlsoEach::poll_next`.
+        let this = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        let mut item = stream.poll_next(cx);
+        if let Poll::Ready(Some(item)) = &mut item {
+            (this.function)(item);
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::pin;
+    use std::task::Waker;
+
+    struct Iter<I>(I);
+
+    impl<I: Iterator + Unpin> Stream for Iter<I> {
+        type Item = I::Item;
+
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<I::Item>> {
+            Poll::Ready(self.0.next())
+        }
+    }
+
+    fn collect<S: Stream>(stream: S) -> Vec<S::Item> {
+        let mut stream = pin!(stream);
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut items = Vec::new();
+        while let Poll::Ready(Some(item)) = stream.as_mut().poll_next(&mut cx) {
+            items.push(item);
+        }
+        items
+    }
+
+    #[test]
+    fn also_each() {
+        let mut seen = Vec::new();
+        let items = collect(Iter(1..4).also_each(|it| seen.push(*it)));
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(seen, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn also_each_mut() {
+        let items = collect(Iter(1..4).also_each_mut(|it| *it *= 10));
+        assert_eq!(items, vec![10, 20, 30]);
+    }
+}
@@ -0,0 +1,238 @@
+//! `apply` and `also` variants for locks, which hold the lock
+//! for the duration of the function.
+
+use std::sync::{ Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError };
+
+pub trait MutexApplyExt<T: ?Sized> {
+    /// Locks this mutex, applies the given function to a mutable
+    /// reference of the value and returns the result. The lock is
+    /// released before returning.
+    ///
+    /// Panics if the mutex is poisoned, see
+    /// [`try_apply_locked`](MutexApplyExt::try_apply_locked).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::MutexApplyExt;
+    /// use std::sync::Mutex;
+    ///
+    /// let counter = Mutex::new(0);
+    /// let x = counter.apply_locked(|it| {
+    ///     *it += 1;
+    ///     *it * 2
+    /// });
+    /// assert_eq!(x, 2);
+    /// ```
+    fn apply_locked<R>(&self, function: impl FnOnce(&mut T) -> R) -> R;
+
+    /// Locks this mutex and applies the given function to a mutable
+    /// reference of the value. Returns this mutex, for chaining.
+    ///
+    /// Panics if the mutex is poisoned.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::MutexApplyExt;
+    /// use std::sync::Mutex;
+    ///
+    /// let list = Mutex::new(vec![3, 1]);
+    /// list.also_locked(|it| it.push(2)).also_locked(|it| it.sort());
+    /// assert_eq!(*list.lock().unwrap(), vec![1, 2, 3]);
+    /// ```
+    fn also_locked(&self, function: impl FnOnce(&mut T)) -> &Self;
+
+    /// Locks this mutex, applies the given function to a mutable
+    /// reference of the value and returns the result. If the mutex is
+    /// poisoned, the function is not called and the error is returned,
+    /// from which the guard can still be recovered.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::MutexApplyExt;
+    /// use std::sync::Mutex;
+    ///
+    /// let counter = Mutex::new(1);
+    /// let x = counter.try_apply_locked(|it| *it + 1);
+    /// assert_eq!(x.ok(), Some(2));
+    /// ```
+    fn try_apply_locked<R>(&self, function: impl FnOnce(&mut T) -> R) -> Result<R, PoisonError<MutexGuard<'_, T>>>;
+}
+
+impl<T: ?Sized> MutexApplyExt<T> for Mutex<T> {
+    #[inline(always)]
+    #[track_caller]
+    fn apply_locked<R>(&self, function: impl FnOnce(&mut T) -> R) -> R {
+        match self.lock() {
+            Ok(mut guard) => function(&mut guard),
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_locked(&self, function: impl FnOnce(&mut T)) -> &Self {
+        self.apply_locked(function);
+        self
+    }
+
+    #[inline(always)]
+    fn try_apply_locked<R>(&self, function: impl FnOnce(&mut T) -> R) -> Result<R, PoisonError<MutexGuard<'_, T>>> {
+        self.lock().map(|mut guard| function(&mut guard))
+    }
+}
+
+pub trait RwLockApplyExt<T: ?Sized> {
+    /// Locks this lock with shared read access, applies the given
+    /// function to a reference of the value and returns the result.
+    /// The lock is released before returning.
+    ///
+    /// Panics if the lock is poisoned, see
+    /// [`try_apply_read`](RwLockApplyExt::try_apply_read).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::RwLockApplyExt;
+    /// use std::sync::RwLock;
+    ///
+    /// let config = RwLock::new(vec![1, 2, 3]);
+    /// assert_eq!(config.apply_read(|it| it.len()), 3);
+    /// ```
+    fn apply_read<R>(&self, function: impl FnOnce(&T) -> R) -> R;
+
+    /// Locks this lock with exclusive write access, applies the given
+    /// function to a mutable reference of the value and returns the
+    /// result. The lock is released before returning.
+    ///
+    /// Panics if the lock is poisoned, see
+    /// [`try_apply_write`](RwLockApplyExt::try_apply_write).
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::RwLockApplyExt;
+    /// use std::sync::RwLock;
+    ///
+    /// let config = RwLock::new(vec![1, 2]);
+    /// let len = config.apply_write(|it| {
+    ///     it.push(3);
+    ///     it.len()
+    /// });
+    /// assert_eq!(len, 3);
+    /// ```
+    fn apply_write<R>(&self, function: impl FnOnce(&mut T) -> R) -> R;
+
+    /// Attempts to lock this lock with shared read access without
+    /// blocking, and applies the given function to a reference of the
+    /// value. If the lock is poisoned or currently held for writing,
+    /// the function is not called and the error is returned.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::RwLockApplyExt;
+    /// use std::sync::RwLock;
+    ///
+    /// let config = RwLock::new(5);
+    /// assert_eq!(config.try_apply_read(|it| *it).ok(), Some(5));
+    /// ```
+    fn try_apply_read<R>(&self, function: impl FnOnce(&T) -> R) -> Result<R, TryLockError<RwLockReadGuard<'_, T>>>;
+
+    /// Attempts to lock this lock with exclusive write access without
+    /// blocking, and applies the given function to a mutable reference
+    /// of the value. If the lock is poisoned or currently held, the
+    /// function is not called and the error is returned.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::RwLockApplyExt;
+    /// use std::sync::RwLock;
+    ///
+    /// let config = RwLock::new(5);
+    /// let _reader = config.read().unwrap();
+    /// assert!(config.try_apply_write(|it| *it += 1).is_err());
+    /// ```
+    fn try_apply_write<R>(&self, function: impl FnOnce(&mut T) -> R) -> Result<R, TryLockError<RwLockWriteGuard<'_, T>>>;
+}
+
+impl<T: ?Sized> RwLockApplyExt<T> for RwLock<T> {
+    #[inline(always)]
+    #[track_caller]
+    fn apply_read<R>(&self, function: impl FnOnce(&T) -> R) -> R {
+        match self.read() {
+            Ok(guard) => function(&guard),
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn apply_write<R>(&self, function: impl FnOnce(&mut T) -> R) -> R {
+        match self.write() {
+            Ok(mut guard) => function(&mut guard),
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    #[inline(always)]
+    fn try_apply_read<R>(&self, function: impl FnOnce(&T) -> R) -> Result<R, TryLockError<RwLockReadGuard<'_, T>>> {
+        self.try_read().map(|guard| function(&guard))
+    }
+
+    #[inline(always)]
+    fn try_apply_write<R>(&self, function: impl FnOnce(&mut T) -> R) -> Result<R, TryLockError<RwLockWriteGuard<'_, T>>> {
+        self.try_write().map(|mut guard| function(&mut guard))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Also;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn poisoned<T: Send + 'static>(value: T) -> Arc<Mutex<T>> {
+        Arc::new(Mutex::new(value)).also(|it| {
+            let mutex = Arc::clone(it);
+            let _ = thread::spawn(move || {
+                let _guard = mutex.lock().unwrap();
+                panic!("poisoning");
+            })
+            .join();
+        })
+    }
+
+    #[test]
+    fn apply_locked() {
+        let mutex = Mutex::new(vec![1]);
+        assert_eq!(mutex.apply_locked(|it| it.len()), 1);
+        mutex.also_locked(|it| it.push(2)).also_locked(|it| it.push(3));
+        assert_eq!(*mutex.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn try_apply_locked() {
+        assert_eq!(Mutex::new(2).try_apply_locked(|it| *it * 2).ok(), Some(4));
+
+        let mutex = poisoned(5);
+        let guard = mutex.try_apply_locked(|_| unreachable!()).unwrap_err().into_inner();
+        assert_eq!(*guard, 5);
+    }
+
+    #[test]
+    fn apply_read_write() {
+        let lock = RwLock::new(vec![1]);
+        assert_eq!(lock.apply_write(|it| it.also_mut(|it| it.push(2)).len()), 2);
+        assert_eq!(lock.apply_read(|it| it.clone()), vec![1, 2]);
+    }
+
+    #[test]
+    fn try_apply_read_write() {
+        let lock = RwLock::new(1);
+        {
+            let _reader = lock.read().unwrap();
+            assert_eq!(lock.try_apply_read(|it| *it).ok(), Some(1));
+            assert!(matches!(lock.try_apply_write(|_| ()), Err(TryLockError::WouldBlock)));
+        }
+        assert!(lock.try_apply_write(|it| *it += 1).is_ok());
+        assert_eq!(*lock.read().unwrap(), 2);
+    }
+}
@@ -0,0 +1,119 @@
+//! Bridges between this crate and the `tap` crate.
+//!
+//! `tap` implements `Tap` and `Pipe` for every type, including the
+//! types of this crate, and the prelude re-exports them, so `tap` and
+//! `pipe` can be mixed with `also` and `apply` directly. The other way
+//! around, [`Tapped`] wraps a value of any type and provides the core
+//! methods of this crate, implemented through `tap`. This also works
+//! without the `blanket` feature, for types of other crates that can
+//! not be opted in with [`impl_apply!`](crate::impl_apply):
+//!
+//! ```
+//! use apply_also::tapped::Tapped;
+//! use tap::Tap;
+//!
+//! let list = Tapped(vec![3, 1, 2]).also_mut(|it| it.sort()).into_inner().tap(|it| assert_eq!(it[0], 1));
+//! assert_eq!(Tapped::from(list).apply(|it| it.len()), 3);
+//! ```
+
+use core::ops::{ Deref, DerefMut };
+use tap::{ Pipe, Tap };
+
+/// A value with the core methods of this crate, implemented through
+/// `tap::Tap` and `tap::Pipe`.
+///
+/// The wrapper dereferences to the value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tapped<T>(pub T);
+
+impl<T> Tapped<T> {
+    /// Returns the wrapped value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Applies the given function to the value and returns the
+    /// result, like [`Apply::apply`](crate::Apply::apply).
+    #[inline(always)]
+    pub fn apply<R>(self, function: impl FnOnce(T) -> R) -> R {
+        self.0.pipe(function)
+    }
+
+    /// Applies the given function to a reference of the value and
+    /// returns the result, like [`Apply::apply_ref`](crate::Apply::apply_ref).
+    #[inline(always)]
+    pub fn apply_ref<R>(self, function: impl FnOnce(&T) -> R) -> R {
+        self.0.pipe_ref(function)
+    }
+
+    /// Applies the given function to a mutable reference of the value
+    /// and returns the result, like [`Apply::apply_mut`](crate::Apply::apply_mut).
+    #[inline(always)]
+    pub fn apply_mut<R>(mut self, function: impl FnOnce(&mut T) -> R) -> R {
+        self.0.pipe_ref_mut(function)
+    }
+
+    /// Applies the given function to a reference of the value and
+    /// returns the wrapper, like [`Also::also`](crate::Also::also).
+    #[inline(always)]
+    pub fn also(self, function: impl FnOnce(&T)) -> Self {
+        Tapped(self.0.tap(function))
+    }
+
+    /// Applies the given function to a mutable reference of the value
+    /// and returns the wrapper, like [`Also::also_mut`](crate::Also::also_mut).
+    #[inline(always)]
+    pub fn also_mut(self, function: impl FnOnce(&mut T)) -> Self {
+        Tapped(self.0.tap_mut(function))
+    }
+}
+
+impl<T> From<T> for Tapped<T> {
+    #[inline(always)]
+    fn from(value: T) -> Self {
+        Tapped(value)
+    }
+}
+
+impl<T> Deref for Tapped<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Tapped<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn core_methods() {
+        let mut log = Vec::new();
+        let x = Tapped(2).also(|it| log.push(*it)).also_mut(|it| *it += 1);
+        assert_eq!((*x, log), (3, vec![2]));
+        assert_eq!(x.apply(|it| it * 2), 6);
+        assert_eq!(Tapped(vec![1, 2]).apply_ref(Vec::len), 2);
+        assert_eq!(Tapped(vec![1]).apply_mut(|it| { it.push(2); it.len() }), 2);
+    }
+
+    #[test]
+    fn mixed_with_tap() {
+        let x = Tapped::from(String::from("a"))
+            .also_mut(|it| it.push('b'))
+            .into_inner()
+            .tap_mut(|it| it.push('c'))
+            .pipe(Tapped)
+            .apply(|it| it.len());
+        assert_eq!(x, 3);
+    }
+}
@@ -0,0 +1,169 @@
+//! `tee`-style adapters for readers and writers, duplicating every
+//! byte passing through to a secondary sink.
+
+use std::io::{ self, Read, Write };
+
+pub trait ReadTeeExt: Read + Sized {
+    /// Wraps this reader, so that every byte read from it is also
+    /// written to the given sink.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ReadTeeExt;
+    /// use std::io::Read;
+    ///
+    /// let mut copy = Vec::new();
+    /// let mut text = String::new();
+    /// b"hello".as_ref().tee_read(&mut copy).read_to_string(&mut text)?;
+    /// assert_eq!(text, "hello");
+    /// assert_eq!(copy, b"hello");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    fn tee_read<W: Write>(self, sink: W) -> TeeReader<Self, W>;
+}
+
+pub trait WriteTeeExt: Write + Sized {
+    /// Wraps this writer, so that every byte written to it is also
+    /// written to the given sink. Only the bytes accepted by this
+    /// writer are passed on.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::WriteTeeExt;
+    /// use std::io::Write;
+    ///
+    /// let (mut out, mut log) = (Vec::new(), Vec::new());
+    /// write!((&mut out).tee_write(&mut log), "{}", 42)?;
+    /// assert_eq!(out, b"42");
+    /// assert_eq!(log, b"42");
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    fn tee_write<W: Write>(self, sink: W) -> TeeWriter<Self, W>;
+}
+
+/// Reader returned by [`ReadTeeExt::tee_read`].
+#[derive(Debug)]
+pub struct TeeReader<R, W> {
+    reader: R,
+    sink: W,
+}
+
+/// Writer returned by [`WriteTeeExt::tee_write`].
+#[derive(Debug)]
+pub struct TeeWriter<W, S> {
+    writer: W,
+    sink: S,
+}
+
+impl<R: Read> ReadTeeExt for R {
+    fn tee_read<W: Write>(self, sink: W) -> TeeReader<Self, W> {
+        TeeReader { reader: self, sink }
+    }
+}
+
+impl<W: Write> WriteTeeExt for W {
+    fn tee_write<S: Write>(self, sink: S) -> TeeWriter<Self, S> {
+        TeeWriter { writer: self, sink }
+    }
+}
+
+impl<R, W> TeeReader<R, W> {
+    /// Returns the wrapped reader and the sink.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.sink)
+    }
+}
+
+impl<W, S> TeeWriter<W, S> {
+    /// Returns the wrapped writer and the sink.
+    pub fn into_inner(self) -> (W, S) {
+        (self.writer, self.sink)
+    }
+}
+
+impl<R: Read, W: Write> Read for TeeReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.sink.write_all(&buf[..read])?;
+        Ok(read)
+    }
+}
+
+impl<W: Write, S: Write> Write for TeeWriter<W, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.sink.write_all(&buf[..written])?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        self.sink.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads or writes at most `limit` bytes at a time.
+    struct Trickle<T> {
+        inner: T,
+        limit: usize,
+    }
+
+    impl<T: Read> Read for Trickle<T> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let limit = self.limit.min(buf.len());
+            self.inner.read(&mut buf[..limit])
+        }
+    }
+
+    impl<T: Write> Write for Trickle<T> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let limit = self.limit.min(buf.len());
+            self.inner.write(&buf[..limit])
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.inner.flush()
+        }
+    }
+
+    #[test]
+    fn tee_reader() {
+        let mut reader = Trickle { inner: &b"partial reads"[..], limit: 3 }.tee_read(Vec::new());
+        let mut buf = [0; 8];
+        assert_eq!(reader.read(&mut buf).unwrap(), 3);
+        assert_eq!(reader.sink, b"par");
+
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        let (_, sink) = reader.into_inner();
+        assert_eq!(rest, b"tial reads");
+        assert_eq!(sink, b"partial reads");
+    }
+
+    #[test]
+    fn tee_writer() {
+        let mut writer = Trickle { inner: Vec::new(), limit: 2 }.tee_write(Vec::new());
+        assert_eq!(writer.write(b"abc").unwrap(), 2);
+        assert_eq!(writer.sink, b"ab");
+
+        writer.write_all(b"cdefg").unwrap();
+        writer.flush().unwrap();
+        let (inner, sink) = writer.into_inner();
+        assert_eq!(inner.inner, b"abcdefg");
+        assert_eq!(sink, b"abcdefg");
+    }
+
+    #[test]
+    fn read_and_write() {
+        let mut cursor = io::Cursor::new(Vec::new());
+        (&mut cursor).tee_write(Vec::new()).write_all(b"both").unwrap();
+        cursor.set_position(0);
+        let mut copy = Vec::new();
+        (&mut cursor).tee_read(&mut copy).read_to_end(&mut Vec::new()).unwrap();
+        assert_eq!(copy, b"both");
+    }
+}
@@ -0,0 +1,17 @@
+//! Helpers shared by the tests.
+
+use core::future::Future;
+use core::pin::pin;
+use core::task::{ Context, Poll, Waker };
+
+/// Polls the given future to completion on the current thread, with a
+/// waker that does nothing.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Utilities for observing values in tests.
+//!
+//! ```
+//! use apply_also::{ Apply, AlsoRecord };
+//! use apply_also::testing::Recorder;
+//!
+//! let recorder = Recorder::new();
+//! let x = 3
+//!     .also_record(&recorder)
+//!     .apply(|it| it * 2)
+//!     .also_record(&recorder);
+//! assert_eq!(x, 6);
+//! recorder.assert_recorded(&[3, 6]);
+//! ```
+
+use crate::ApplyAlso;
+use core::cell::{ Cell, RefCell };
+use core::fmt::{ self, Debug };
+use std::sync::{ Mutex, MutexGuard, PoisonError };
+
+/// Records snapshots of values passed to [`also_record`](AlsoRecord::also_record),
+/// in order.
+///
+/// A recorder can be shared between threads.
+pub struct Recorder<T> {
+    snapshots: Mutex<Vec<T>>,
+}
+
+impl<T> Recorder<T> {
+    /// Creates an empty recorder.
+    pub fn new() -> Self {
+        Recorder { snapshots: Mutex::new(Vec::new()) }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<T>> {
+        self.snapshots.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records a snapshot.
+    pub fn record(&self, snapshot: T) {
+        self.lock().push(snapshot);
+    }
+
+    /// Returns the number of recorded snapshots.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns whether no snapshots were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns a copy of the recorded snapshots.
+    pub fn snapshots(&self) -> Vec<T> where T: Clone {
+        self.lock().clone()
+    }
+
+    /// Removes and returns the recorded snapshots.
+    pub fn take(&self) -> Vec<T> {
+        core::mem::take(&mut *self.lock())
+    }
+
+    /// Returns the recorded snapshots.
+    pub fn into_snapshots(self) -> Vec<T> {
+        self.snapshots.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Asserts that exactly the expected snapshots were recorded, in order.
+    #[track_caller]
+    pub fn assert_recorded(&self, expected: &[T]) where T: PartialEq + Debug {
+        let snapshots = self.lock();
+        assert_eq!(snapshots.as_slice(), expected, "unexpected recorded snapshots");
+    }
+}
+
+impl<T> Default for Recorder<T> {
+    fn default() -> Self {
+        Recorder::new()
+    }
+}
+
+impl<T: Debug> Debug for Recorder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder").field("snapshots", &*self.lock()).finish()
+    }
+}
+
+pub trait AlsoRecord<T> {
+    /// Records a clone of this value in the given recorder.
+    /// Returns the original value.
+    fn also_record(self, recorder: &Recorder<T>) -> T where T: Clone + Debug;
+}
+
+impl<T: ApplyAlso> AlsoRecord<T> for T {
+    #[inline(always)]
+    fn also_record(self, recorder: &Recorder<T>) -> T where T: Clone + Debug {
+        recorder.record(self.clone());
+        self
+    }
+}
+
+/// Wraps a function, counting how often it is called and, for `Clone`
+/// arguments, recording them, to verify how a combinator uses a
+/// function.
+///
+/// [`CallSpy::new`] wraps a function taking its argument by value,
+/// passed on with [`as_fn`](CallSpy::as_fn), and [`CallSpy::new_ref`]
+/// one taking it by reference, passed on with [`as_ref_fn`](CallSpy::as_ref_fn).
+/// These only count the calls, so the argument can be of any type.
+/// [`as_recording_fn`](CallSpy::as_recording_fn) and
+/// [`as_recording_ref_fn`](CallSpy::as_recording_ref_fn) also record a
+/// clone of each argument.
+///
+/// Example:
+/// ```
+/// # use apply_also::{ Apply, Also };
+/// # use apply_also::testing::CallSpy;
+/// let double = CallSpy::new(|it: i32| it * 2);
+/// let log = CallSpy::new_ref(|_: &i32| {});
+/// let x = 3.apply(double.as_recording_fn()).also(log.as_ref_fn()).also(log.as_ref_fn());
+/// assert_eq!(x, 6);
+/// double.assert_called_with(&[3]);
+/// log.assert_called(2);
+/// ```
+pub struct CallSpy<A, F> {
+    function: RefCell<F>,
+    calls: Cell<usize>,
+    args: RefCell<Vec<A>>,
+}
+
+impl<A, F> CallSpy<A, F> {
+    /// Wraps the given function, which takes its argument by value.
+    pub fn new<R>(function: F) -> Self where F: FnMut(A) -> R {
+        CallSpy::wrap(function)
+    }
+
+    /// Wraps the given function, which takes its argument by reference.
+    pub fn new_ref<R>(function: F) -> Self where F: FnMut(&A) -> R {
+        CallSpy::wrap(function)
+    }
+
+    fn wrap(function: F) -> Self {
+        CallSpy {
+            function: RefCell::new(function),
+            calls: Cell::new(0),
+            args: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns a function calling the wrapped function and counting
+    /// the call.
+    pub fn as_fn<R>(&self) -> impl FnMut(A) -> R + '_ where F: FnMut(A) -> R {
+        move |argument| {
+            self.calls.set(self.calls.get() + 1);
+            (self.function.borrow_mut())(argument)
+        }
+    }
+
+    /// Like [`as_fn`](CallSpy::as_fn), for a function taking its
+    /// argument by reference.
+    pub fn as_ref_fn<R>(&self) -> impl FnMut(&A) -> R + '_ where F: FnMut(&A) -> R {
+        move |argument| {
+            self.calls.set(self.calls.get() + 1);
+            (self.function.borrow_mut())(argument)
+        }
+    }
+
+    /// Like [`as_fn`](CallSpy::as_fn), but also records a clone of the
+    /// argument.
+    pub fn as_recording_fn<R>(&self) -> impl FnMut(A) -> R + '_ where F: FnMut(A) -> R, A: Clone {
+        let mut function = self.as_fn();
+        move |argument| {
+            self.args.borrow_mut().push(argument.clone());
+            function(argument)
+        }
+    }
+
+    /// Like [`as_ref_fn`](CallSpy::as_ref_fn), but also records a clone
+    /// of the argument.
+    pub fn as_recording_ref_fn<R>(&self) -> impl FnMut(&A) -> R + '_ where F: FnMut(&A) -> R, A: Clone {
+        let mut function = self.as_ref_fn();
+        move |argument| {
+            self.args.borrow_mut().push(argument.clone());
+            function(argument)
+        }
+    }
+
+    /// Returns how often the function was called.
+    pub fn calls(&self) -> usize {
+        self.calls.get()
+    }
+
+    /// Returns the recorded arguments of all calls, in order.
+    pub fn args(&self) -> Vec<A> where A: Clone {
+        self.args.borrow().clone()
+    }
+
+    /// Asserts that the function was called exactly `n` times.
+    #[track_caller]
+    pub fn assert_called(&self, n: usize) {
+        assert_eq!(self.calls(), n, "unexpected number of calls");
+    }
+
+    /// Asserts that the recorded arguments are exactly the expected
+    /// arguments, in order.
+    #[track_caller]
+    pub fn assert_called_with(&self, expected: &[A]) where A: PartialEq + Debug {
+        assert_eq!(self.args.borrow().as_slice(), expected, "unexpected call arguments");
+    }
+}
+
+impl<A: Debug, F> Debug for CallSpy<A, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallSpy")
+            .field("calls", &self.calls.get())
+            .field("args", &*self.args.borrow())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ Also, Apply };
+
+    #[test]
+    fn also_record() {
+        let recorder = Recorder::new();
+        let x = vec![1]
+            .also_record(&recorder)
+            .also_mut(|it| it.push(2))
+            .also_record(&recorder);
+        assert_eq!(x, vec![1, 2]);
+        assert_eq!(recorder.len(), 2);
+        recorder.assert_recorded(&[vec![1], vec![1, 2]]);
+        assert_eq!(format!("{:?}", recorder), "Recorder { snapshots: [[1], [1, 2]] }");
+
+        assert_eq!(recorder.take(), vec![vec![1], vec![1, 2]]);
+        assert!(recorder.is_empty());
+        vec![3].also_record(&recorder);
+        assert_eq!(recorder.into_snapshots(), vec![vec![3]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected recorded snapshots")]
+    fn assert_recorded() {
+        let recorder = Recorder::new();
+        1.also_record(&recorder);
+        recorder.assert_recorded(&[2]);
+    }
+
+    #[test]
+    fn call_spy() {
+        let spy = CallSpy::new(|it: String| it.len());
+        assert_eq!(["a".to_string(), "bc".to_string()].map(spy.as_recording_fn()), [1, 2]);
+        spy.assert_called(2);
+        assert_eq!(spy.args(), vec!["a", "bc"]);
+        assert_eq!(format!("{:?}", spy), r#"CallSpy { calls: 2, args: ["a", "bc"], .. }"#);
+
+        let mut seen = 0;
+        let spy = CallSpy::new_ref(|it: &i32| seen += it);
+        let x = 5.also(spy.as_recording_ref_fn()).also_if(false, spy.as_recording_ref_fn());
+        assert_eq!(x, 5);
+        spy.assert_called_with(&[5]);
+        drop(spy);
+        assert_eq!(seen, 5);
+    }
+
+    #[test]
+    fn call_spy_not_clone() {
+        struct Token;
+
+        let spy = CallSpy::new(|_: Token| 1);
+        let spy_ref = CallSpy::new_ref(|_: &Token| {});
+        assert_eq!(Token.also(spy_ref.as_ref_fn()).apply(spy.as_fn()), 1);
+        spy.assert_called(1);
+        spy_ref.assert_called(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected number of calls")]
+    fn assert_called() {
+        let spy = CallSpy::new(|_: ()| {});
+        spy.assert_called(1);
+    }
+}
@@ -0,0 +1,303 @@
+//! `apply` and `also` variants measuring how long the function takes.
+
+use crate::ApplyAlso;
+use core::fmt;
+use core::panic::Location;
+use std::time::{ Duration, Instant };
+
+/// Severity of a reported message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Severity {
+    Info,
+    Warn,
+}
+
+#[cfg(test)]
+std::thread_local! {
+    /// The messages reported on this thread, checked by the tests.
+    static REPORTED: core::cell::RefCell<Vec<(Severity, String)>> = const { core::cell::RefCell::new(Vec::new()) };
+}
+
+/// Reports a message through `tracing` or `log`, whichever is enabled,
+/// or prints it to stderr otherwise.
+pub(crate) fn report(severity: Severity, location: &'static Location<'static>, args: fmt::Arguments<'_>) {
+    #[cfg(test)]
+    REPORTED.with(|it| it.borrow_mut().push((severity, args.to_string())));
+
+    #[cfg(feature = "tracing")]
+    match severity {
+        Severity::Info => tracing::info!(file = location.file(), line = location.line(), "{}", args),
+        Severity::Warn => tracing::warn!(file = location.file(), line = location.line(), "{}", args),
+    }
+
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    crate::logging::log(
+        match severity {
+            Severity::Info => log::Level::Info,
+            Severity::Warn => log::Level::Warn,
+        },
+        crate::logging::TARGET,
+        location,
+        args,
+    );
+
+    #[cfg(not(any(feature = "log", feature = "tracing")))]
+    match severity {
+        Severity::Info => eprintln!("[{}:{}:{}] {}", location.file(), location.line(), location.column(), args),
+        Severity::Warn => eprintln!("[{}:{}:{}] warning: {}", location.file(), location.line(), location.column(), args),
+    }
+}
+
+pub trait ApplyTimed<T> {
+    /// Applies the given function to this value and returns the
+    /// result, together with the time the function took.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyTimed;
+    /// let (x, elapsed) = 256.apply_measure(|it| it * 2);
+    /// assert_eq!(x, 512);
+    /// println!("took {:?}", elapsed);
+    /// ```
+    fn apply_measure<R>(self, function: impl FnOnce(T) -> R) -> (R, Duration);
+
+    /// Applies the given function to a reference of this value.
+    /// Returns the original value, together with the time the
+    /// function took.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyTimed;
+    /// let (x, elapsed) = vec![3, 1, 2].also_measure(|it| {
+    ///     println!("{:?}", it);
+    /// });
+    /// assert_eq!(x, vec![3, 1, 2]);
+    /// ```
+    fn also_measure(self, function: impl FnOnce(&T)) -> (T, Duration);
+
+    /// Applies the given function to a reference of this value and
+    /// reports the time it took under the given label, through
+    /// `tracing` or `log` if enabled, or to stderr otherwise.
+    /// Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyTimed;
+    /// // reports something like "validate took 1.2µs"
+    /// let x = 3.also_timed("validate", |it| assert!(*it > 0));
+    /// assert_eq!(x, 3);
+    /// ```
+    fn also_timed(self, label: &str, function: impl FnOnce(&T)) -> T;
+
+    /// Applies the given function to a mutable reference of this value
+    /// and reports the time it took under the given label, like
+    /// [`also_timed`](ApplyTimed::also_timed). Returns the mutated value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyTimed;
+    /// let x = vec![3, 1, 2].also_mut_timed("sort", |it| it.sort());
+    /// assert_eq!(x, vec![1, 2, 3]);
+    /// ```
+    fn also_mut_timed(self, label: &str, function: impl FnOnce(&mut T)) -> T;
+
+    /// Applies the given function to a reference of this value and
+    /// emits a warning if it takes longer than the given threshold,
+    /// through `tracing` or `log` if enabled, or to stderr otherwise.
+    /// Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyTimed;
+    /// use std::time::Duration;
+    ///
+    /// let x = 3.also_warn_if_slower_than(Duration::from_millis(10), |it| {
+    ///     println!("checking {}", it);
+    /// });
+    /// assert_eq!(x, 3);
+    /// ```
+    fn also_warn_if_slower_than(self, threshold: Duration, function: impl FnOnce(&T)) -> T;
+
+    /// Applies the given function to a mutable reference of this value
+    /// and emits a warning if it takes longer than the given threshold,
+    /// like [`also_warn_if_slower_than`](ApplyTimed::also_warn_if_slower_than).
+    /// Returns the mutated value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyTimed;
+    /// use std::time::Duration;
+    ///
+    /// let x = Vec::new().also_mut_warn_if_slower_than(Duration::from_millis(10), |it| {
+    ///     it.push(1);
+    /// });
+    /// assert_eq!(x, vec![1]);
+    /// ```
+    fn also_mut_warn_if_slower_than(self, threshold: Duration, function: impl FnOnce(&mut T)) -> T;
+
+    /// Applies the given function to this value and records the time
+    /// it took, in seconds, in the given Prometheus histogram.
+    /// Returns the result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyTimed;
+    /// use prometheus::{ Histogram, HistogramOpts };
+    ///
+    /// let latency = Histogram::with_opts(HistogramOpts::new("parse_seconds", "Parse latency")).unwrap();
+    /// let x = "42".apply_observed(&latency, str::parse::<i32>);
+    /// assert_eq!(x, Ok(42));
+    /// assert_eq!(latency.get_sample_count(), 1);
+    /// ```
+    #[cfg(feature = "prometheus")]
+    fn apply_observed<R>(self, histogram: &prometheus::Histogram, function: impl FnOnce(T) -> R) -> R;
+}
+
+fn warn_if_slower_than(threshold: Duration, elapsed: Duration, location: &'static Location<'static>) {
+    if elapsed > threshold {
+        report(
+            Severity::Warn,
+            location,
+            format_args!("took {:?}, exceeding the threshold of {:?}", elapsed, threshold),
+        );
+    }
+}
+
+impl<T: ApplyAlso> ApplyTimed<T> for T {
+    #[inline(always)]
+    fn apply_measure<R>(self, function: impl FnOnce(T) -> R) -> (R, Duration) {
+        let start = Instant::now();
+        let result = function(self);
+        (result, start.elapsed())
+    }
+
+    #[inline(always)]
+    fn also_measure(self, function: impl FnOnce(&T)) -> (T, Duration) {
+        let start = Instant::now();
+        function(&self);
+        (self, start.elapsed())
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_timed(self, label: &str, function: impl FnOnce(&T)) -> T {
+        let (value, elapsed) = self.also_measure(function);
+        report(Severity::Info, Location::caller(), format_args!("{} took {:?}", label, elapsed));
+        value
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_mut_timed(mut self, label: &str, function: impl FnOnce(&mut T)) -> T {
+        let start = Instant::now();
+        function(&mut self);
+        let elapsed = start.elapsed();
+        report(Severity::Info, Location::caller(), format_args!("{} took {:?}", label, elapsed));
+        self
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_warn_if_slower_than(self, threshold: Duration, function: impl FnOnce(&T)) -> T {
+        let (value, elapsed) = self.also_measure(function);
+        warn_if_slower_than(threshold, elapsed, Location::caller());
+        value
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn also_mut_warn_if_slower_than(mut self, threshold: Duration, function: impl FnOnce(&mut T)) -> T {
+        let start = Instant::now();
+        function(&mut self);
+        warn_if_slower_than(threshold, start.elapsed(), Location::caller());
+        self
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[inline(always)]
+    fn apply_observed<R>(self, histogram: &prometheus::Histogram, function: impl FnOnce(T) -> R) -> R {
+        let (result, elapsed) = self.apply_measure(function);
+        histogram.observe(elapsed.as_secs_f64());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn apply_measure() {
+        let (x, elapsed) = 5.apply_measure(|it| {
+            thread::sleep(Duration::from_millis(5));
+            it + 1
+        });
+        assert_eq!(x, 6);
+        assert!(elapsed >= Duration::from_millis(5));
+
+        let (y, elapsed) = "a".also_measure(|_| thread::sleep(Duration::from_millis(5)));
+        assert_eq!(y, "a");
+        assert!(elapsed >= Duration::from_millis(5));
+    }
+
+    /// Returns and clears the messages reported on this thread.
+    fn reported() -> Vec<(Severity, String)> {
+        REPORTED.with(|it| it.take())
+    }
+
+    /// Whether the message is the label followed by a `Debug` duration.
+    fn is_timing(message: &str, label: &str) -> bool {
+        message
+            .strip_prefix(label)
+            .and_then(|it| it.strip_prefix(" took "))
+            .is_some_and(|it| it.starts_with(|c: char| c.is_ascii_digit()) && it.ends_with('s'))
+    }
+
+    #[test]
+    fn also_timed() {
+        reported();
+        assert_eq!(1.also_timed("noop", |_| thread::sleep(Duration::from_millis(1))), 1);
+        assert_eq!(vec![2, 1].also_mut_timed("sort", |it| it.sort()), vec![1, 2]);
+
+        let reports = reported();
+        assert_eq!(reports.len(), 2);
+        assert_eq!((reports[0].0, reports[1].0), (Severity::Info, Severity::Info));
+        assert!(is_timing(&reports[0].1, "noop"), "{}", reports[0].1);
+        assert!(is_timing(&reports[1].1, "sort"), "{}", reports[1].1);
+    }
+
+    #[test]
+    fn also_warn_if_slower_than() {
+        reported();
+        let x = 1.also_warn_if_slower_than(Duration::from_millis(1), |_| {
+            thread::sleep(Duration::from_millis(2));
+        });
+        assert_eq!(x, 1);
+        let reports = reported();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].0, Severity::Warn);
+        assert!(reports[0].1.ends_with("exceeding the threshold of 1ms"), "{}", reports[0].1);
+
+        let y = vec![1].also_mut_warn_if_slower_than(Duration::from_secs(60), |it| it.push(2));
+        assert_eq!(y, vec![1, 2]);
+        let z = 2.also_warn_if_slower_than(Duration::from_secs(60), |_| ());
+        assert_eq!(z, 2);
+        assert_eq!(reported(), vec![]);
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[test]
+    fn apply_observed() {
+        use prometheus::{ Histogram, HistogramOpts };
+
+        let histogram = Histogram::with_opts(HistogramOpts::new("test_seconds", "test")).unwrap();
+        let x = 2.apply_observed(&histogram, |it| {
+            thread::sleep(Duration::from_millis(5));
+            it * 2
+        });
+        assert_eq!(x, 4);
+        assert_eq!(histogram.get_sample_count(), 1);
+        assert!(histogram.get_sample_sum() >= 0.005);
+    }
+}
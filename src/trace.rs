@@ -0,0 +1,226 @@
+//! `apply` and `also` variants integrating with `tracing` spans.
+
+use crate::ApplyAlso;
+use core::fmt::Debug;
+use tracing::field;
+use tracing::Span;
+
+pub trait AlsoTrace<T> {
+    /// Records the `Debug` representation of this value as the given
+    /// field of the current span. Returns the original value.
+    ///
+    /// As with `Span::record`, the field has to be declared when the
+    /// span is created, e.g. with `field::Empty`, or nothing is recorded.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::{ Apply, AlsoTrace };
+    /// use tracing::{ field, info_span };
+    ///
+    /// let span = info_span!("load", count = field::Empty);
+    /// let _guard = span.enter();
+    /// let x = vec![1, 2, 3].apply_ref(Vec::len).also_record("count");
+    /// assert_eq!(x, 3);
+    /// ```
+    fn also_record(self, field: &str) -> T where T: Debug;
+
+    /// Records the `Debug` representation of this value as the given
+    /// field of the given span. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoTrace;
+    /// use tracing::{ field, info_span };
+    ///
+    /// let span = info_span!("load", user = field::Empty);
+    /// let x = "alice".also_record_in(&span, "user");
+    /// assert_eq!(x, "alice");
+    /// ```
+    fn also_record_in(self, span: &Span, field: &str) -> T where T: Debug;
+
+    /// Applies the given function to this value while the given span
+    /// is entered and returns the result. Consumes the value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoTrace;
+    /// use tracing::info_span;
+    ///
+    /// let x = 256.apply_in_span(&info_span!("double"), |it| {
+    ///     tracing::info!("doubling {}", it);
+    ///     it * 2
+    /// });
+    /// assert_eq!(x, 512);
+    /// ```
+    fn apply_in_span<R>(self, span: &Span, function: impl FnOnce(T) -> R) -> R;
+
+    /// Applies the given function to a reference of this value while
+    /// the given span is entered. Returns the original value.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::AlsoTrace;
+    /// use tracing::info_span;
+    ///
+    /// let x = 3.also_in_span(&info_span!("validate"), |it| {
+    ///     tracing::info!("validating {}", it);
+    /// });
+    /// assert_eq!(x, 3);
+    /// ```
+    fn also_in_span(self, span: &Span, function: impl FnOnce(&T)) -> T;
+}
+
+impl<T: ApplyAlso> AlsoTrace<T> for T {
+    #[inline(always)]
+    fn also_record(self, field: &str) -> T where T: Debug {
+        Span::current().record(field, field::debug(&self));
+        self
+    }
+
+    #[inline(always)]
+    fn also_record_in(self, span: &Span, field: &str) -> T where T: Debug {
+        span.record(field, field::debug(&self));
+        self
+    }
+
+    #[inline(always)]
+    fn apply_in_span<R>(self, span: &Span, function: impl FnOnce(T) -> R) -> R {
+        span.in_scope(|| function(self))
+    }
+
+    #[inline(always)]
+    fn also_in_span(self, span: &Span, function: impl FnOnce(&T)) -> T {
+        span.in_scope(|| function(&self));
+        self
+    }
+}
+
+/// Emits a `tracing` event with the `Debug` representation of the
+/// given value as its `value` field, and returns the value.
+/// The level defaults to `DEBUG`.
+///
+/// Example:
+/// ```
+/// # use apply_also::{ also_trace, Apply };
+/// use tracing::Level;
+///
+/// let x = also_trace!(256.apply(|it| it * 2));
+/// let y = also_trace!(Level::INFO, x + 1);
+/// assert_eq!(y, 513);
+/// ```
+#[macro_export]
+macro_rules! also_trace {
+    ($value:expr $(,)?) => {
+        $crate::also_trace!($crate::__tracing::Level::DEBUG, $value)
+    };
+    ($level:expr, $value:expr $(,)?) => {
+        match $value {
+            value => {
+                $crate::__tracing::event!($level, value = ?value);
+                value
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{ Arc, Mutex };
+    use tracing::field::{ Field, Visit };
+    use tracing::span::{ Attributes, Id, Record };
+    use tracing::{ info_span, Event, Metadata, Subscriber };
+    use tracing_core::span::Current;
+
+    /// Collects every recorded field as `(name, value)`.
+    #[derive(Clone, Default)]
+    struct Collector {
+        fields: Arc<Mutex<Vec<(String, String)>>>,
+        spans: Arc<Mutex<Vec<&'static Metadata<'static>>>>,
+        entered: Arc<Mutex<Vec<Id>>>,
+    }
+
+    impl Visit for Collector {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.fields.lock().unwrap().push((field.name().to_string(), format!("{:?}", value)));
+        }
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(attributes.metadata());
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            values.record(&mut self.clone());
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, span: &Id) {
+            self.entered.lock().unwrap().push(span.clone());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+
+        fn current_span(&self) -> Current {
+            match self.entered.lock().unwrap().last() {
+                Some(id) => Current::new(id.clone(), self.spans.lock().unwrap()[id.into_u64() as usize - 1]),
+                None => Current::none(),
+            }
+        }
+    }
+
+    fn collect(function: impl FnOnce()) -> Vec<(String, String)> {
+        let collector = Collector::default();
+        tracing::subscriber::with_default(collector.clone(), function);
+        let fields = collector.fields.lock().unwrap().clone();
+        fields
+    }
+
+    #[test]
+    fn also_record() {
+        let fields = collect(|| {
+            let span = info_span!("test", count = field::Empty, name = field::Empty);
+            let x = span.in_scope(|| 3.also_record("count"));
+            let y = "alice".also_record_in(&span, "name");
+            assert_eq!((x, y), (3, "alice"));
+        });
+        assert_eq!(fields, vec![
+            ("count".to_string(), "3".to_string()),
+            ("name".to_string(), "\"alice\"".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn apply_in_span() {
+        let fields = collect(|| {
+            let span = info_span!("test", doubled = field::Empty);
+            let x = 2.apply_in_span(&span, |it| (it * 2).also_record("doubled"));
+            assert_eq!(x, 4);
+            assert_eq!(5.also_in_span(&span, |it| assert_eq!(*it, 5)), 5);
+        });
+        assert_eq!(fields, vec![("doubled".to_string(), "4".to_string())]);
+    }
+
+    #[test]
+    fn also_trace() {
+        let fields = collect(|| {
+            let x = also_trace!(vec![1]);
+            assert_eq!(x, vec![1]);
+        });
+        assert_eq!(fields, vec![("value".to_string(), "[1]".to_string())]);
+    }
+}
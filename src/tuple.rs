@@ -0,0 +1,117 @@
+//! `apply` variants spreading tuples into function arguments, and
+//! combining pairs of values.
+
+use crate::ApplyAlso;
+
+pub trait Apply2<A, B> {
+    /// Applies the given function to the elements of this pair and
+    /// returns the result. Consumes the pair.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Apply2;
+    /// let x = "key=value".split_once('=').unwrap().apply2(|key, value| format!("{}: {}", key, value));
+    /// assert_eq!(x, "key: value");
+    /// ```
+    fn apply2<R>(self, function: impl FnOnce(A, B) -> R) -> R;
+}
+
+pub trait Apply3<A, B, C> {
+    /// Applies the given function to the elements of this triple and
+    /// returns the result. Consumes the triple.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Apply3;
+    /// let volume = (2, 3, 4).apply3(|x, y, z| x * y * z);
+    /// assert_eq!(volume, 24);
+    /// ```
+    fn apply3<R>(self, function: impl FnOnce(A, B, C) -> R) -> R;
+}
+
+pub trait ApplyBoth<T> {
+    /// Applies the given function to both elements of this pair and
+    /// returns the pair of results. Consumes the pair.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyBoth;
+    /// let (min, max) = ("3", "14").both(|it| it.parse::<i32>().unwrap());
+    /// assert_eq!(max - min, 11);
+    /// ```
+    fn both<U>(self, function: impl FnMut(T) -> U) -> (U, U);
+}
+
+pub trait ApplyZip<T> {
+    /// Applies the given function to this value and the other value
+    /// and returns the result. Consumes both values.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyZip;
+    /// let greeting = "hello".apply_zip("world", |a, b| format!("{} {}", a, b));
+    /// assert_eq!(greeting, "hello world");
+    /// ```
+    fn apply_zip<U, R>(self, other: U, function: impl FnOnce(T, U) -> R) -> R;
+}
+
+impl<A, B> Apply2<A, B> for (A, B) {
+    #[inline(always)]
+    fn apply2<R>(self, function: impl FnOnce(A, B) -> R) -> R {
+        function(self.0, self.1)
+    }
+}
+
+impl<A, B, C> Apply3<A, B, C> for (A, B, C) {
+    #[inline(always)]
+    fn apply3<R>(self, function: impl FnOnce(A, B, C) -> R) -> R {
+        function(self.0, self.1, self.2)
+    }
+}
+
+impl<T> ApplyBoth<T> for (T, T) {
+    #[inline(always)]
+    fn both<U>(self, mut function: impl FnMut(T) -> U) -> (U, U) {
+        let first = function(self.0);
+        (first, function(self.1))
+    }
+}
+
+impl<T: ApplyAlso> ApplyZip<T> for T {
+    #[inline(always)]
+    fn apply_zip<U, R>(self, other: U, function: impl FnOnce(T, U) -> R) -> R {
+        function(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply2() {
+        assert_eq!((1, "a").apply2(|n, s| s.repeat(n)), "a");
+        assert_eq!((3, 4).apply2(i32::max), 4);
+    }
+
+    #[test]
+    fn apply3() {
+        assert_eq!((1, 2, 3).apply3(|a, b, c| [c, b, a]), [3, 2, 1]);
+    }
+
+    #[test]
+    fn both() {
+        let mut order = Vec::new();
+        let x = (1, 2).both(|it| {
+            order.push(it);
+            it * 10
+        });
+        assert_eq!(x, (10, 20));
+        assert_eq!(order, vec![1, 2]);
+    }
+
+    #[test]
+    fn apply_zip() {
+        assert_eq!(3.apply_zip("x", |n, s| s.repeat(n)), "xxx");
+    }
+}
@@ -0,0 +1,88 @@
+//! `apply` and `also` variants catching panics of the function.
+
+use crate::ApplyAlso;
+use std::any::Any;
+use std::boxed::Box;
+use std::panic::{ self, RefUnwindSafe, UnwindSafe };
+
+pub trait ApplyCatch<T> {
+    /// Applies the given function to this value and returns the result,
+    /// or the panic payload if the function panics. Consumes the value.
+    ///
+    /// This uses `std::panic::catch_unwind`, so the same caveats apply:
+    /// panics are only caught with `panic = "unwind"`, and the panic hook
+    /// still runs.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyCatch;
+    /// let x = 256.apply_catch(|it| it * 2);
+    /// assert_eq!(x.ok(), Some(512));
+    ///
+    /// let y = 0.apply_catch(|it: i32| 1 / it);
+    /// assert!(y.is_err());
+    /// ```
+    fn apply_catch<R>(self, function: impl FnOnce(T) -> R + UnwindSafe) -> Result<R, Box<dyn Any + Send>>
+    where
+        T: UnwindSafe;
+
+    /// Applies the given function to a reference of this value.
+    /// Returns the original value, together with the panic payload
+    /// if the function panicked.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::ApplyCatch;
+    /// let (x, result) = vec![1, 2].also_catch(|it| {
+    ///     assert_eq!(it.len(), 3, "unexpected length");
+    /// });
+    /// assert_eq!(x, vec![1, 2]);
+    /// assert!(result.is_err());
+    /// ```
+    fn also_catch(self, function: impl FnOnce(&T) + UnwindSafe) -> (T, Result<(), Box<dyn Any + Send>>)
+    where
+        T: RefUnwindSafe;
+}
+
+impl<T: ApplyAlso> ApplyCatch<T> for T {
+    #[inline(always)]
+    fn apply_catch<R>(self, function: impl FnOnce(T) -> R + UnwindSafe) -> Result<R, Box<dyn Any + Send>>
+    where
+        T: UnwindSafe,
+    {
+        panic::catch_unwind(move || function(self))
+    }
+
+    #[inline(always)]
+    fn also_catch(self, function: impl FnOnce(&T) + UnwindSafe) -> (T, Result<(), Box<dyn Any + Send>>)
+    where
+        T: RefUnwindSafe,
+    {
+        let result = panic::catch_unwind(|| function(&self));
+        (self, result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_catch() {
+        assert_eq!(2.apply_catch(|it| it + 1).ok(), Some(3));
+
+        let payload = "boom".apply_catch(|it| -> i32 { panic!("{}", it) }).unwrap_err();
+        assert_eq!(payload.downcast_ref::<String>().map(String::as_str), Some("boom"));
+    }
+
+    #[test]
+    fn also_catch() {
+        let (x, result) = 5.also_catch(|_| ());
+        assert_eq!(x, 5);
+        assert!(result.is_ok());
+
+        let (y, result) = String::from("kept").also_catch(|_| panic!("plugin failed"));
+        assert_eq!(y, "kept");
+        assert_eq!(result.unwrap_err().downcast_ref::<&str>(), Some(&"plugin failed"));
+    }
+}
@@ -0,0 +1,144 @@
+//! Updating a value behind a mutable reference with a function taking
+//! the value by value.
+//!
+//! The value is moved out of the reference while the function runs,
+//! leaving a placeholder behind. If the function panics, the
+//! placeholder stays in place, so the reference always points to a
+//! valid value and no unsafe code is needed.
+
+use crate::ApplyAlso;
+use core::mem;
+
+pub trait Update<T> {
+    /// Replaces this value with the result of the given function,
+    /// applied to the value. If the function panics, the value is left
+    /// as `T::default()`.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Update;
+    /// #[derive(Debug, Default, PartialEq)]
+    /// enum State { #[default] Idle, Running(u32) }
+    ///
+    /// impl State {
+    ///     fn next(self) -> State {
+    ///         match self {
+    ///             State::Idle => State::Running(0),
+    ///             State::Running(n) => State::Running(n + 1),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut state = State::Idle;
+    /// state.update(State::next);
+    /// state.update(State::next);
+    /// assert_eq!(state, State::Running(1));
+    /// ```
+    fn update(&mut self, function: impl FnOnce(T) -> T) where T: Default;
+
+    /// Replaces this value with the result of the given function,
+    /// applied to the value. If the function panics, the value is left
+    /// as the given placeholder.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Update;
+    /// let mut path = std::path::PathBuf::from("/usr");
+    /// path.update_or(Default::default(), |it| it.join("bin"));
+    /// assert_eq!(path.to_str(), Some("/usr/bin"));
+    /// ```
+    fn update_or(&mut self, placeholder: T, function: impl FnOnce(T) -> T);
+
+    /// Replaces this value with the given new value, then applies the
+    /// given function to the old value and returns the result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Update;
+    /// let mut buffer = vec![1, 2, 3];
+    /// let sum = buffer.apply_replace(Vec::with_capacity(3), |old| old.iter().sum::<i32>());
+    /// assert_eq!(sum, 6);
+    /// assert!(buffer.is_empty());
+    /// ```
+    fn apply_replace<R>(&mut self, new: T, function: impl FnOnce(T) -> R) -> R;
+
+    /// Takes this value, leaving `T::default()` in its place, then
+    /// applies the given function to it and returns the result.
+    ///
+    /// Example:
+    /// ```
+    /// # use apply_also::Update;
+    /// let mut pending = vec!["a", "b"];
+    /// let flushed = pending.apply_take(|it| it.len());
+    /// assert_eq!(flushed, 2);
+    /// assert!(pending.is_empty());
+    /// ```
+    fn apply_take<R>(&mut self, function: impl FnOnce(T) -> R) -> R where T: Default;
+}
+
+impl<T: ApplyAlso> Update<T> for T {
+    #[inline(always)]
+    fn update(&mut self, function: impl FnOnce(T) -> T) where T: Default {
+        *self = function(mem::take(self));
+    }
+
+    #[inline(always)]
+    fn update_or(&mut self, placeholder: T, function: impl FnOnce(T) -> T) {
+        *self = function(mem::replace(self, placeholder));
+    }
+
+    #[inline(always)]
+    fn apply_replace<R>(&mut self, new: T, function: impl FnOnce(T) -> R) -> R {
+        function(mem::replace(self, new))
+    }
+
+    #[inline(always)]
+    fn apply_take<R>(&mut self, function: impl FnOnce(T) -> R) -> R where T: Default {
+        function(mem::take(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{ self, AssertUnwindSafe };
+
+    #[test]
+    fn update() {
+        let mut x = vec![1, 2];
+        x.update(|mut it| {
+            it.push(3);
+            it
+        });
+        assert_eq!(x, vec![1, 2, 3]);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| x.update(|_| panic!("failed"))));
+        assert!(result.is_err());
+        assert!(x.is_empty());
+    }
+
+    #[test]
+    fn update_or() {
+        let mut x = "a".to_string();
+        x.update_or(String::new(), |it| it + "b");
+        assert_eq!(x, "ab");
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| x.update_or("poisoned".into(), |_| panic!("failed"))));
+        assert!(result.is_err());
+        assert_eq!(x, "poisoned");
+    }
+
+    #[test]
+    fn apply_replace() {
+        let mut x = 1;
+        assert_eq!(x.apply_replace(2, |old| old * 10), 10);
+        assert_eq!(x, 2);
+    }
+
+    #[test]
+    fn apply_take() {
+        let mut x = Some("a".to_string());
+        assert_eq!(x.apply_take(|old| old.map(|it| it + "b")), Some("ab".to_string()));
+        assert_eq!(x, None);
+    }
+}
@@ -0,0 +1,109 @@
+//! Codegen regression tests: every combinator has to compile to
+//! exactly the same machine code as calling the function directly.
+//!
+//! Each case is compiled twice with `rustc`, once using the plain
+//! call and once using the combinator, and the assembly of the
+//! resulting `subject` function is compared.
+
+use std::env;
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::process::Command;
+
+const PRELUDE: &str = r#"
+#![allow(unused)]
+use apply_also::*;
+
+#[inline(never)]
+fn double(x: u64) -> u64 { x.wrapping_mul(2) }
+
+#[inline(never)]
+fn observe(x: &u64) { std::hint::black_box(x); }
+
+#[inline(never)]
+fn bump(x: &mut u64) { *x = std::hint::black_box(*x + 1); }
+
+#[inline(never)]
+fn take(x: &mut u64) -> u64 { std::mem::replace(x, 0) }
+"#;
+
+/// (name, signature, direct body, combinator body)
+const CASES: &[(&str, &str, &str, &str)] = &[
+    ("apply", "(x: u64) -> u64", "double(x)", "x.apply(double)"),
+    ("apply_ref", "(v: Vec<u8>) -> usize", "v.len()", "v.apply_ref(Vec::len)"),
+    ("apply_mut", "(x: u64) -> u64", "{ let mut x = x; take(&mut x) }", "x.apply_mut(take)"),
+    ("also", "(x: u64) -> u64", "{ observe(&x); x }", "x.also(observe)"),
+    ("also_mut", "(x: u64) -> u64", "{ let mut x = x; bump(&mut x); x }", "x.also_mut(bump)"),
+    ("apply_if", "(x: u64, c: bool) -> u64", "if c { double(x) } else { x }", "x.apply_if(c, double)"),
+    ("also_if", "(x: u64, c: bool) -> u64", "{ if c { observe(&x); } x }", "x.also_if(c, observe)"),
+    ("try_apply", "(x: u64) -> Option<u64>", "x.checked_mul(3).ok_or(()).ok()", "x.try_apply(|x| x.checked_mul(3).ok_or(())).ok()"),
+    ("take_if", "(x: u64) -> Option<u64>", "if x > 3 { Some(x) } else { None }", "x.take_if(|x| *x > 3)"),
+    ("also_by_mut", "(x: &mut u64) -> &mut u64", "{ bump(x); x }", "x.also_by_mut(bump)"),
+];
+
+fn rustc() -> String {
+    env::var("RUSTC").unwrap_or_else(|_| "rustc".into())
+}
+
+/// Builds the library in release mode into a separate target
+/// directory and returns the path of the resulting rlib.
+fn build_library(target_dir: &Path) -> PathBuf {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let status = Command::new(cargo)
+        .args(["build", "--release", "--lib", "--quiet"])
+        .arg("--manifest-path")
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .env("CARGO_TARGET_DIR", target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "failed to build the library");
+    target_dir.join("release").join("libapply_also.rlib")
+}
+
+/// Compiles the given body as the `subject` function and returns
+/// its assembly.
+fn assembly(dir: &Path, library: &Path, name: &str, signature: &str, body: &str, opt_level: u8) -> String {
+    let source = dir.join(format!("{}.rs", name));
+    let output = dir.join(format!("{}.s", name));
+    fs::write(
+        &source,
+        format!("{}\n#[no_mangle]\npub fn subject{} {{ {} }}\n", PRELUDE, signature, body),
+    ).unwrap();
+
+    let status = Command::new(rustc())
+        .args(["--edition", "2018", "--crate-type", "lib", "--crate-name", "case", "--emit", "asm"])
+        .arg(format!("-Copt-level={}", opt_level))
+        .arg("-Cdebuginfo=0")
+        .arg("--extern")
+        .arg(format!("apply_also={}", library.display()))
+        .arg("-o")
+        .arg(&output)
+        .arg(&source)
+        .status()
+        .expect("failed to run rustc");
+    assert!(status.success(), "failed to compile case `{}`", name);
+
+    let asm = fs::read_to_string(&output).unwrap();
+    let start = asm.find("subject:").expect("symbol `subject` not found");
+    let end = asm[start..].find(".Lfunc_end").map_or(asm.len(), |end| start + end);
+    asm[start..end].to_string()
+}
+
+#[test]
+fn combinators_compile_to_direct_calls() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("codegen");
+    fs::create_dir_all(&dir).unwrap();
+    let library = build_library(&dir.join("target"));
+
+    for opt_level in &[2, 3] {
+        for (name, signature, direct, combinator) in CASES {
+            let expected = assembly(&dir, &library, &format!("{}_direct", name), signature, direct, *opt_level);
+            let actual = assembly(&dir, &library, &format!("{}_combinator", name), signature, combinator, *opt_level);
+            assert_eq!(
+                expected, actual,
+                "`{}` does not compile to the same code as `{}` at opt-level {}",
+                combinator, direct, opt_level,
+            );
+        }
+    }
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "derive")]
+
+use apply_also::With;
+
+#[derive(Debug, Default, PartialEq, With)]
+struct Config {
+    retries: u32,
+    name: String,
+    pub verbose: bool,
+}
+
+#[derive(Debug, Default, PartialEq, With)]
+struct Wrapper<T: Default> {
+    inner: T,
+}
+
+#[test]
+fn with_setters() {
+    let config = Config::default()
+        .with_retries(3)
+        .with_name("server".to_string())
+        .with_verbose(true);
+    assert_eq!(config, Config { retries: 3, name: "server".to_string(), verbose: true });
+}
+
+#[test]
+fn with_generic_struct() {
+    let wrapper = Wrapper::default().with_inner(vec![1]);
+    assert_eq!(wrapper.inner, vec![1]);
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "derive")]
+
+use apply_also::{ extension, Also };
+
+#[extension]
+fn shrink(v: &mut Vec<u8>) {
+    v.shrink_to_fit();
+}
+
+#[extension]
+fn pad(v: &mut Vec<u8>, len: usize, byte: u8) {
+    v.resize(len, byte);
+}
+
+// `is_multiple_of` would need Rust 1.87.
+#[allow(clippy::manual_is_multiple_of)]
+#[extension]
+fn is_even(x: &u32) -> bool {
+    x % 2 == 0
+}
+
+#[extension]
+fn word_count(s: &str) -> usize {
+    s.split_whitespace().count()
+}
+
+#[extension]
+fn check_ascii(s: &str) {
+    assert!(s.is_ascii());
+}
+
+#[extension]
+fn zero(bytes: &mut [u8]) {
+    bytes.iter_mut().for_each(|it| *it = 0);
+}
+
+#[extension]
+fn byte_len<T: AsRef<[u8]> + ?Sized>(value: &T) -> usize {
+    value.as_ref().len()
+}
+
+#[extension(Describe)]
+fn describe<T: std::fmt::Debug>(value: T) -> String {
+    format!("<{:?}>", value)
+}
+
+#[test]
+fn also_extension() {
+    let buffer = Vec::with_capacity(64).also_mut(|it| it.push(1)).shrink_also();
+    assert_eq!(buffer.capacity(), 1);
+
+    let padded = vec![1].pad_also(3, 0).also_mut(shrink);
+    assert_eq!(padded, vec![1, 0, 0]);
+}
+
+#[test]
+fn apply_extension() {
+    assert!(4.is_even_apply());
+    assert!(!3.is_even_apply());
+    assert_eq!(5.describe_apply(), "<5>");
+}
+
+#[test]
+fn unsized_extension() {
+    assert_eq!("apply also".word_count_apply(), 2);
+    assert_eq!(String::from("a b c").as_str().word_count_apply(), 3);
+    assert_eq!("ascii".check_ascii_also().len(), 5);
+    assert_eq!("four".byte_len_apply(), 4);
+
+    let mut buffer = [1u8, 2, 3];
+    assert_eq!(buffer[1..].zero_also(), &[0, 0]);
+    assert_eq!(buffer, [1, 0, 0]);
+}
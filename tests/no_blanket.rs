@@ -0,0 +1,68 @@
+#![cfg(not(feature = "blanket"))]
+
+use apply_also::{ impl_apply, Also, AlsoRef, Apply, ApplyChain, ApplyTimed, ApplyZip, Update };
+
+#[derive(Debug, Default, PartialEq)]
+struct Config {
+    retries: u32,
+}
+
+struct Wrapper<T>(T);
+
+struct Other;
+
+impl_apply!(Config);
+impl_apply!(impl<T> Wrapper<T>);
+
+// Evaluates to whether the type implements the trait, without failing
+// to compile if it does not: the inherent constant is only visible if
+// its bound holds, and shadows the one of the fallback trait.
+macro_rules! implements {
+    ($ty:ty: $($bound:tt)+) => {{
+        #[allow(dead_code)]
+        trait Fallback {
+            const IMPLEMENTS: bool = false;
+        }
+
+        struct Probe<T: ?Sized>(core::marker::PhantomData<T>);
+
+        impl<T: ?Sized> Fallback for Probe<T> {}
+
+        #[allow(dead_code)]
+        impl<T: ?Sized + $($bound)+> Probe<T> {
+            const IMPLEMENTS: bool = true;
+        }
+
+        Probe::<$ty>::IMPLEMENTS
+    }};
+}
+
+#[test]
+fn opted_in() {
+    let config = Config::default().also_mut(|it| it.retries = 3);
+    assert_eq!(config, Config { retries: 3 });
+    assert_eq!(Wrapper(2).apply(|it| it.0 * 2), 4);
+    let mut wrapper = Wrapper(vec![1]);
+    wrapper.also_by_mut(|it| it.0.push(2));
+    assert_eq!(wrapper.0, vec![1, 2]);
+}
+
+#[test]
+fn opted_in_extensions() {
+    let mut config = Config::default().apply_fold([|it: Config| Config { retries: it.retries + 1 }]);
+    config.update(|it| Config { retries: it.retries * 2 });
+    assert_eq!(config, Config { retries: 2 });
+    let retries = config.apply_zip(3, |it, extra| it.retries + extra);
+    assert_eq!(retries, 5);
+}
+
+#[test]
+fn not_opted_in() {
+    assert!(implements!(Config: Also<Config>));
+    assert!(implements!(Config: ApplyTimed<Config>));
+    assert!(!implements!(Other: Also<Other>));
+    assert!(!implements!(Other: ApplyTimed<Other>));
+    assert!(!implements!(Other: ApplyZip<Other>));
+    assert!(!implements!(u32: Apply<u32, u32>));
+    assert!(!implements!(Vec<u8>: ApplyChain<Vec<u8>>));
+}
@@ -0,0 +1,42 @@
+//! Calls methods of different traits through the glob prelude only, to
+//! make sure that none of their names are ambiguous.
+
+#![cfg(feature = "blanket")]
+
+use apply_also::prelude::*;
+use core::cell::RefCell;
+
+#[test]
+fn core_methods() {
+    let x = vec![3, 1, 2].also_mut(|it| it.sort()).apply(|it| it[0]);
+    assert_eq!(x, 1);
+    let cell = RefCell::new(vec![1]);
+    cell.also_borrow_mut(|it| it.push(2));
+    assert_eq!(cell.into_inner().also_borrow_mut_as(<[i32]>::reverse), vec![2, 1]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn tee() {
+    use std::io::{ Cursor, Read, Write };
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut log = Vec::new();
+    (&mut cursor).tee_write(&mut log).write_all(b"tee").unwrap();
+    cursor.set_position(0);
+    let mut copy = Vec::new();
+    (&mut cursor).tee_read(&mut copy).read_to_end(&mut Vec::new()).unwrap();
+    assert_eq!((log, copy), (b"tee".to_vec(), b"tee".to_vec()));
+}
+
+#[cfg(all(feature = "compat", feature = "std"))]
+#[test]
+fn compat() {
+    use std::io::Write;
+
+    let mut out = Vec::new();
+    let writer = (&mut out).k_tee(|it| assert!(it.is_empty()));
+    writer.tee_write(Vec::new()).write_all(b"k").unwrap();
+    assert_eq!(out, b"k");
+    assert_eq!(2.k_apply(|it| it + 1).k_also(|it| assert_eq!(*it, 3)), 3);
+}